@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+use async_trait::async_trait;
+use crate::client::VyperClient;
+use crate::errors::VyperError;
+use crate::types::*;
+
+/// The REST endpoints of the Vyper API.
+///
+/// [`VyperClient`] implements this trait. Code that only needs the endpoints
+/// can depend on `VyperApi` (or `Arc<dyn VyperApi>`) and be unit tested with
+/// [`MockVyperApi`], which is generated by `mockall` and available with the
+/// `testing` cargo feature.
+#[cfg_attr(any(test, feature = "testing"), mockall::automock)]
+#[async_trait]
+pub trait VyperApi: Send + Sync {
+    async fn get_chain_ids(&self) -> Result<HashMap<String, ChainId>, VyperError>;
+    async fn get_token_ath(&self, chain_id: ChainId, market_id: &str) -> Result<TokenATH, VyperError>;
+    async fn get_token_market(&self, market_id: &str, chain_id: ChainId, interval: Interval) -> Result<TokenPair, VyperError>;
+    async fn get_token_holders(&self, market_id: &str, chain_id: ChainId) -> Result<TokenHolders, VyperError>;
+    async fn get_token_holders_page(&self, market_id: &str, chain_id: ChainId, params: TokenHoldersParams) -> Result<TokenHolders, VyperError>;
+    async fn get_token_markets(&self, token_mint: &str, chain_id: ChainId) -> Result<Vec<TokenMarket>, VyperError>;
+    async fn get_wallet_holdings(&self, wallet_address: &str, chain_id: ChainId) -> Result<Vec<WalletHolding>, VyperError>;
+    async fn get_wallet_aggregated_pnl(&self, wallet_address: &str, chain_id: ChainId) -> Result<WalletAggregatedPnL, VyperError>;
+    async fn get_wallet_pnl(&self, wallet_address: &str, market_id: &str, chain_id: ChainId) -> Result<WalletPnL, VyperError>;
+    async fn get_token_metadata(&self, chain_id: ChainId, token_mint: &str) -> Result<TokenMetadata, VyperError>;
+    async fn get_token_symbol(&self, chain_id: ChainId, token_mint: &str) -> Result<TokenSymbol, VyperError>;
+    async fn get_top_traders(&self, market_id: &str, chain_id: ChainId) -> Result<Vec<TopTrader>, VyperError>;
+    async fn search_tokens(&self, criteria: &str, chain_id: Option<ChainId>) -> Result<Vec<TokenSearchResult>, VyperError>;
+    async fn get_token_pairs(&self, params: TokenPairsParams) -> Result<TokenPairs, VyperError>;
+}
+
+#[async_trait]
+impl VyperApi for VyperClient {
+    async fn get_chain_ids(&self) -> Result<HashMap<String, ChainId>, VyperError> {
+        VyperClient::get_chain_ids(self).await
+    }
+
+    async fn get_token_ath(&self, chain_id: ChainId, market_id: &str) -> Result<TokenATH, VyperError> {
+        VyperClient::get_token_ath(self, chain_id, market_id).await
+    }
+
+    async fn get_token_market(&self, market_id: &str, chain_id: ChainId, interval: Interval) -> Result<TokenPair, VyperError> {
+        VyperClient::get_token_market(self, market_id, chain_id, interval).await
+    }
+
+    async fn get_token_holders(&self, market_id: &str, chain_id: ChainId) -> Result<TokenHolders, VyperError> {
+        VyperClient::get_token_holders(self, market_id, chain_id).await
+    }
+
+    async fn get_token_holders_page(&self, market_id: &str, chain_id: ChainId, params: TokenHoldersParams) -> Result<TokenHolders, VyperError> {
+        VyperClient::get_token_holders_page(self, market_id, chain_id, params).await
+    }
+
+    async fn get_token_markets(&self, token_mint: &str, chain_id: ChainId) -> Result<Vec<TokenMarket>, VyperError> {
+        VyperClient::get_token_markets(self, token_mint, chain_id).await
+    }
+
+    async fn get_wallet_holdings(&self, wallet_address: &str, chain_id: ChainId) -> Result<Vec<WalletHolding>, VyperError> {
+        VyperClient::get_wallet_holdings(self, wallet_address, chain_id).await
+    }
+
+    async fn get_wallet_aggregated_pnl(&self, wallet_address: &str, chain_id: ChainId) -> Result<WalletAggregatedPnL, VyperError> {
+        VyperClient::get_wallet_aggregated_pnl(self, wallet_address, chain_id).await
+    }
+
+    async fn get_wallet_pnl(&self, wallet_address: &str, market_id: &str, chain_id: ChainId) -> Result<WalletPnL, VyperError> {
+        VyperClient::get_wallet_pnl(self, wallet_address, market_id, chain_id).await
+    }
+
+    async fn get_token_metadata(&self, chain_id: ChainId, token_mint: &str) -> Result<TokenMetadata, VyperError> {
+        VyperClient::get_token_metadata(self, chain_id, token_mint).await
+    }
+
+    async fn get_token_symbol(&self, chain_id: ChainId, token_mint: &str) -> Result<TokenSymbol, VyperError> {
+        VyperClient::get_token_symbol(self, chain_id, token_mint).await
+    }
+
+    async fn get_top_traders(&self, market_id: &str, chain_id: ChainId) -> Result<Vec<TopTrader>, VyperError> {
+        VyperClient::get_top_traders(self, market_id, chain_id).await
+    }
+
+    async fn search_tokens(&self, criteria: &str, chain_id: Option<ChainId>) -> Result<Vec<TokenSearchResult>, VyperError> {
+        VyperClient::search_tokens(self, criteria, chain_id).await
+    }
+
+    async fn get_token_pairs(&self, params: TokenPairsParams) -> Result<TokenPairs, VyperError> {
+        VyperClient::get_token_pairs(self, params).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockall::predicate::*;
+    use std::sync::Arc;
+
+    async fn describe_token(api: &dyn VyperApi, chain_id: ChainId, token_mint: &str) -> Result<String, VyperError> {
+        let symbol = api.get_token_symbol(chain_id, token_mint).await?;
+        Ok(format!("{} on {}", symbol.symbol, chain_id))
+    }
+
+    #[tokio::test]
+    async fn test_mock_vyper_api() {
+        let mut api = MockVyperApi::new();
+        api.expect_get_token_symbol()
+            .with(eq(ChainId::Solana), eq("mint"))
+            .times(1)
+            .returning(|_, _| Ok(TokenSymbol { symbol: "MOCK".to_string() }));
+
+        let description = describe_token(&api, ChainId::Solana, "mint").await.unwrap();
+        assert_eq!(description, "MOCK on solana");
+    }
+
+    #[tokio::test]
+    async fn test_mock_vyper_api_errors() {
+        let mut api = MockVyperApi::new();
+        api.expect_get_chain_ids()
+            .returning(|| Err(VyperError::AuthenticationError("invalid key".to_string())));
+
+        let api: Arc<dyn VyperApi> = Arc::new(api);
+        assert!(matches!(api.get_chain_ids().await, Err(VyperError::AuthenticationError(_))));
+    }
+
+    #[test]
+    fn test_client_is_object_safe_api() {
+        let client = VyperClient::new("test_api_key");
+        let _api: Arc<dyn VyperApi> = Arc::new(client);
+    }
+}
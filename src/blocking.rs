@@ -0,0 +1,347 @@
+//! A synchronous wrapper around the async [`VyperClient`](crate::client::VyperClient).
+//!
+//! Enabled with the `blocking` cargo feature. Each call drives the async client
+//! to completion on a runtime owned by the blocking client, so retries, rate
+//! limiting and cassettes behave exactly as they do in async code and errors
+//! are the same [`VyperError`] values. The runtime is multi-threaded, so
+//! clones used from several threads make their requests concurrently and
+//! paginated iterators keep prefetching between calls to `next`.
+//!
+//! Like `reqwest::blocking`, these methods must not be called from inside an
+//! async runtime; doing so panics.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use futures_util::stream::{BoxStream, StreamExt};
+use tokio::runtime::Runtime;
+use crate::client::{self, VyperClientBuilder};
+use crate::errors::VyperError;
+use crate::options::RequestOptions;
+use crate::pagination::PaginationOptions;
+use crate::portfolio::WalletPortfolio;
+use crate::response::Response;
+use crate::search::{MultiChainSearch, SearchRanking};
+use crate::types::*;
+
+/// Background threads of the runtime behind a blocking client. Each call is
+/// driven on the calling thread; the workers only run spawned tasks such as
+/// page prefetches.
+const RUNTIME_WORKER_THREADS: usize = 2;
+
+/// Blocking counterpart of [`client::VyperClient`] with the same method set.
+///
+/// Cloning is cheap; clones share the runtime, connection pool, rate limiter
+/// and cassette.
+#[derive(Clone)]
+pub struct VyperClient {
+    inner: client::VyperClient,
+    runtime: Arc<Runtime>,
+}
+
+impl VyperClient {
+    /// Creates a client against the production API with default settings.
+    ///
+    /// Panics if the HTTP client or the runtime cannot be initialised; use
+    /// [`VyperClientBuilder::build_blocking`] to handle that case as an error instead.
+    pub fn new(api_key: &str) -> Self {
+        Self::builder()
+            .api_key(api_key)
+            .build_blocking()
+            .expect("failed to build blocking Vyper client")
+    }
+
+    /// Returns the shared builder; finish it with
+    /// [`VyperClientBuilder::build_blocking`].
+    pub fn builder() -> VyperClientBuilder {
+        VyperClientBuilder::default()
+    }
+
+    /// Wraps an already configured async client.
+    pub fn from_async(inner: client::VyperClient) -> Result<Self, VyperError> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(RUNTIME_WORKER_THREADS)
+            .thread_name("vyper-blocking")
+            .enable_all()
+            .build()
+            .map_err(|e| VyperError::ConfigurationError(format!("failed to start runtime: {}", e)))?;
+        Ok(Self {
+            inner,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// The async client this wrapper drives.
+    pub fn as_async(&self) -> &client::VyperClient {
+        &self.inner
+    }
+
+    pub fn base_url(&self) -> &str {
+        self.inner.base_url()
+    }
+
+    /// Returns a copy of this client whose requests use `options`. See
+    /// [`client::VyperClient::with_options`].
+    pub fn with_options(&self, options: RequestOptions) -> Self {
+        Self {
+            inner: self.inner.with_options(options),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    pub fn get_chain_ids(&self) -> Result<HashMap<String, ChainId>, VyperError> {
+        self.runtime.block_on(self.inner.get_chain_ids())
+    }
+
+    pub fn get_chain_ids_with_meta(&self) -> Result<Response<HashMap<String, ChainId>>, VyperError> {
+        self.runtime.block_on(self.inner.get_chain_ids_with_meta())
+    }
+
+    pub fn get_token_ath(&self, chain_id: ChainId, market_id: &str) -> Result<TokenATH, VyperError> {
+        self.runtime.block_on(self.inner.get_token_ath(chain_id, market_id))
+    }
+
+    pub fn get_token_ath_with_meta(&self, chain_id: ChainId, market_id: &str) -> Result<Response<TokenATH>, VyperError> {
+        self.runtime.block_on(self.inner.get_token_ath_with_meta(chain_id, market_id))
+    }
+
+    pub fn get_token_market(&self, market_id: &str, chain_id: ChainId, interval: Interval) -> Result<TokenPair, VyperError> {
+        self.runtime.block_on(self.inner.get_token_market(market_id, chain_id, interval))
+    }
+
+    pub fn get_token_market_with_meta(&self, market_id: &str, chain_id: ChainId, interval: Interval) -> Result<Response<TokenPair>, VyperError> {
+        self.runtime.block_on(self.inner.get_token_market_with_meta(market_id, chain_id, interval))
+    }
+
+    pub fn get_token_holders(&self, market_id: &str, chain_id: ChainId) -> Result<TokenHolders, VyperError> {
+        self.runtime.block_on(self.inner.get_token_holders(market_id, chain_id))
+    }
+
+    pub fn get_token_holders_with_meta(&self, market_id: &str, chain_id: ChainId) -> Result<Response<TokenHolders>, VyperError> {
+        self.runtime.block_on(self.inner.get_token_holders_with_meta(market_id, chain_id))
+    }
+
+    pub fn get_token_holders_page(&self, market_id: &str, chain_id: ChainId, params: TokenHoldersParams) -> Result<TokenHolders, VyperError> {
+        self.runtime.block_on(self.inner.get_token_holders_page(market_id, chain_id, params))
+    }
+
+    pub fn get_token_holders_page_with_meta(&self, market_id: &str, chain_id: ChainId, params: TokenHoldersParams) -> Result<Response<TokenHolders>, VyperError> {
+        self.runtime.block_on(self.inner.get_token_holders_page_with_meta(market_id, chain_id, params))
+    }
+
+    pub fn get_token_markets(&self, token_mint: &str, chain_id: ChainId) -> Result<Vec<TokenMarket>, VyperError> {
+        self.runtime.block_on(self.inner.get_token_markets(token_mint, chain_id))
+    }
+
+    pub fn get_token_markets_with_meta(&self, token_mint: &str, chain_id: ChainId) -> Result<Response<Vec<TokenMarket>>, VyperError> {
+        self.runtime.block_on(self.inner.get_token_markets_with_meta(token_mint, chain_id))
+    }
+
+    pub fn get_wallet_holdings(&self, wallet_address: &str, chain_id: ChainId) -> Result<Vec<WalletHolding>, VyperError> {
+        self.runtime.block_on(self.inner.get_wallet_holdings(wallet_address, chain_id))
+    }
+
+    pub fn get_wallet_holdings_with_meta(&self, wallet_address: &str, chain_id: ChainId) -> Result<Response<Vec<WalletHolding>>, VyperError> {
+        self.runtime.block_on(self.inner.get_wallet_holdings_with_meta(wallet_address, chain_id))
+    }
+
+    pub fn get_wallet_aggregated_pnl(&self, wallet_address: &str, chain_id: ChainId) -> Result<WalletAggregatedPnL, VyperError> {
+        self.runtime.block_on(self.inner.get_wallet_aggregated_pnl(wallet_address, chain_id))
+    }
+
+    pub fn get_wallet_aggregated_pnl_with_meta(&self, wallet_address: &str, chain_id: ChainId) -> Result<Response<WalletAggregatedPnL>, VyperError> {
+        self.runtime.block_on(self.inner.get_wallet_aggregated_pnl_with_meta(wallet_address, chain_id))
+    }
+
+    pub fn get_wallet_pnl(&self, wallet_address: &str, market_id: &str, chain_id: ChainId) -> Result<WalletPnL, VyperError> {
+        self.runtime.block_on(self.inner.get_wallet_pnl(wallet_address, market_id, chain_id))
+    }
+
+    pub fn get_wallet_pnl_with_meta(&self, wallet_address: &str, market_id: &str, chain_id: ChainId) -> Result<Response<WalletPnL>, VyperError> {
+        self.runtime.block_on(self.inner.get_wallet_pnl_with_meta(wallet_address, market_id, chain_id))
+    }
+
+    pub fn get_token_metadata(&self, chain_id: ChainId, token_mint: &str) -> Result<TokenMetadata, VyperError> {
+        self.runtime.block_on(self.inner.get_token_metadata(chain_id, token_mint))
+    }
+
+    pub fn get_token_metadata_with_meta(&self, chain_id: ChainId, token_mint: &str) -> Result<Response<TokenMetadata>, VyperError> {
+        self.runtime.block_on(self.inner.get_token_metadata_with_meta(chain_id, token_mint))
+    }
+
+    pub fn get_token_symbol(&self, chain_id: ChainId, token_mint: &str) -> Result<TokenSymbol, VyperError> {
+        self.runtime.block_on(self.inner.get_token_symbol(chain_id, token_mint))
+    }
+
+    pub fn get_token_symbol_with_meta(&self, chain_id: ChainId, token_mint: &str) -> Result<Response<TokenSymbol>, VyperError> {
+        self.runtime.block_on(self.inner.get_token_symbol_with_meta(chain_id, token_mint))
+    }
+
+    pub fn get_top_traders(&self, market_id: &str, chain_id: ChainId) -> Result<Vec<TopTrader>, VyperError> {
+        self.runtime.block_on(self.inner.get_top_traders(market_id, chain_id))
+    }
+
+    pub fn get_top_traders_with_meta(&self, market_id: &str, chain_id: ChainId) -> Result<Response<Vec<TopTrader>>, VyperError> {
+        self.runtime.block_on(self.inner.get_top_traders_with_meta(market_id, chain_id))
+    }
+
+    pub fn search_tokens(&self, criteria: &str, chain_id: Option<ChainId>) -> Result<Vec<TokenSearchResult>, VyperError> {
+        self.runtime.block_on(self.inner.search_tokens(criteria, chain_id))
+    }
+
+    pub fn search_tokens_with_meta(&self, criteria: &str, chain_id: Option<ChainId>) -> Result<Response<Vec<TokenSearchResult>>, VyperError> {
+        self.runtime.block_on(self.inner.search_tokens_with_meta(criteria, chain_id))
+    }
+
+    pub fn get_token_pairs(&self, params: TokenPairsParams) -> Result<TokenPairs, VyperError> {
+        self.runtime.block_on(self.inner.get_token_pairs(params))
+    }
+
+    pub fn get_token_pairs_with_meta(&self, params: TokenPairsParams) -> Result<Response<TokenPairs>, VyperError> {
+        self.runtime.block_on(self.inner.get_token_pairs_with_meta(params))
+    }
+
+    /// See [`client::VyperClient::get_wallet_portfolio`].
+    pub fn get_wallet_portfolio(&self, wallet_address: &str, chains: &[ChainId]) -> Result<WalletPortfolio, VyperError> {
+        self.runtime.block_on(self.inner.get_wallet_portfolio(wallet_address, chains))
+    }
+
+    /// See [`client::VyperClient::search_tokens_across_chains`].
+    pub fn search_tokens_across_chains(&self, criteria: &str, chains: &[ChainId], ranking: &SearchRanking) -> Result<MultiChainSearch, VyperError> {
+        self.runtime.block_on(self.inner.search_tokens_across_chains(criteria, chains, ranking))
+    }
+
+    /// Iterates over every pair matching `params`, fetching pages lazily as the
+    /// iterator advances. See [`client::VyperClient::token_pairs_stream`].
+    pub fn token_pairs_iter(&self, params: TokenPairsParams) -> BlockingIter<TokenPair> {
+        self.token_pairs_iter_with(params, PaginationOptions::default())
+    }
+
+    /// Iterates over every holder of a market. See
+    /// [`client::VyperClient::token_holders_stream`].
+    pub fn token_holders_iter(&self, market_id: &str, chain_id: ChainId) -> BlockingIter<TokenHolder> {
+        self.token_holders_iter_with(market_id, chain_id, TokenHoldersParams::default(), PaginationOptions::default())
+    }
+
+    pub fn token_holders_iter_with(
+        &self,
+        market_id: &str,
+        chain_id: ChainId,
+        params: TokenHoldersParams,
+        options: PaginationOptions,
+    ) -> BlockingIter<TokenHolder> {
+        BlockingIter {
+            stream: self.inner.token_holders_stream_with(market_id, chain_id, params, options),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    pub fn token_pairs_iter_with(&self, params: TokenPairsParams, options: PaginationOptions) -> BlockingIter<TokenPair> {
+        BlockingIter {
+            stream: self.inner.token_pairs_stream_with(params, options),
+            runtime: self.runtime.clone(),
+        }
+    }
+}
+
+/// Iterator over a paginated endpoint, returned by
+/// [`VyperClient::token_pairs_iter`] and [`VyperClient::token_holders_iter`].
+pub struct BlockingIter<T> {
+    stream: BoxStream<'static, Result<T, VyperError>>,
+    runtime: Arc<Runtime>,
+}
+
+impl<T> Iterator for BlockingIter<T> {
+    type Item = Result<T, VyperError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runtime.block_on(self.stream.next())
+    }
+}
+
+impl VyperClientBuilder {
+    /// Builds a [`blocking::VyperClient`](VyperClient) with the configured settings.
+    pub fn build_blocking(self) -> Result<VyperClient, VyperError> {
+        VyperClient::from_async(self.build()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::retry::RetryPolicy;
+    use mockito::{mock, server_url};
+
+    fn setup_client() -> VyperClient {
+        VyperClient::builder()
+            .api_key("test_api_key")
+            .base_url(server_url())
+            .retry_policy(RetryPolicy::none())
+            .build_blocking()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_get_token_symbol() {
+        let _m = mock("GET", "/api/v1/token/symbol")
+            .match_query(mockito::Matcher::UrlEncoded("tokenMint".into(), "blocking-mint".into()))
+            .with_status(200)
+            .with_body(r#"{"status":"success","message":"ok","data":{"symbol":"SYNC"}}"#)
+            .create();
+
+        let symbol = setup_client().get_token_symbol(ChainId::Solana, "blocking-mint").unwrap();
+        assert_eq!(symbol.symbol, "SYNC");
+    }
+
+    fn mock_holders_page(page: i32, wallet: &str) -> mockito::Mock {
+        let holders = serde_json::json!([{"walletAddress": wallet, "tokenHoldings": 1.0, "usdHoldings": 1.0, "percentOwned": 0.1}]);
+        mock("GET", "/api/v1/token/holders")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("marketID".into(), "blocking-prefetch".into()),
+                mockito::Matcher::UrlEncoded("page".into(), page.to_string()),
+            ]))
+            .with_status(200)
+            .with_body(serde_json::json!({"status": "success", "message": "ok", "data": {"holders": holders, "total_holders": 2}}).to_string())
+            .create()
+    }
+
+    #[test]
+    fn test_prefetch_runs_between_calls() {
+        let _first = mock_holders_page(1, "a");
+        let second = mock_holders_page(2, "b");
+        let params = TokenHoldersParams {
+            page: None,
+            limit: Some(1),
+        };
+        let options = PaginationOptions {
+            prefetch: true,
+            ..Default::default()
+        };
+
+        let mut holders = setup_client().token_holders_iter_with("blocking-prefetch", ChainId::Solana, params, options);
+        assert_eq!(holders.next().unwrap().unwrap().wallet_address, "a");
+
+        // The next page is fetched in the background, not inside `next`.
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while !second.matched() && std::time::Instant::now() < deadline {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert!(second.matched());
+        assert_eq!(holders.next().unwrap().unwrap().wallet_address, "b");
+        assert!(holders.next().is_none());
+    }
+
+    #[test]
+    fn test_errors_are_mapped_like_async_client() {
+        let _m = mock("GET", "/api/v1/token/metadata")
+            .match_query(mockito::Matcher::UrlEncoded("tokenMint".into(), "blocking-unauthorized".into()))
+            .with_status(401)
+            .with_body(r#"{"message":"invalid api key"}"#)
+            .create();
+
+        let result = setup_client().get_token_metadata(ChainId::Solana, "blocking-unauthorized");
+        match result {
+            Err(VyperError::AuthenticationError(message)) => assert_eq!(message, "invalid api key"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,347 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use crate::errors::VyperError;
+use crate::response::RawResponse;
+
+const REDACTED: &str = "[REDACTED]";
+const REDACTED_HEADERS: [&str; 5] = ["x-api-key", "authorization", "proxy-authorization", "cookie", "set-cookie"];
+
+/// Whether a [`Cassette`] captures live traffic or serves it back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Requests go to the server and every exchange is appended to the file.
+    Record,
+    /// Requests are answered from the file without touching the network.
+    Replay,
+}
+
+/// Record-and-replay transport for deterministic tests.
+///
+/// Attach it with [`VyperClientBuilder::cassette`](crate::client::VyperClientBuilder::cassette).
+/// Requests are matched on method, path and query parameters (the base URL is
+/// ignored, so a cassette recorded against production replays against any
+/// host). Recorded interactions are served in order; once an interaction has
+/// been used it is not matched again, and a request without an unused match
+/// fails with [`VyperError::CassetteError`]. Credential headers such as
+/// `X-API-Key`, `Authorization` and `Set-Cookie` are redacted on both requests
+/// and responses before anything is written to disk.
+#[derive(Debug, Clone)]
+pub struct Cassette {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    path: PathBuf,
+    mode: CassetteMode,
+    state: Mutex<State>,
+    write_lock: tokio::sync::Mutex<()>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    interactions: Vec<Interaction>,
+    used: Vec<bool>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct CassetteFile {
+    interactions: Vec<Interaction>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    #[serde(default)]
+    pub query: Vec<(String, String)>,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    pub status: u16,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    pub body: String,
+}
+
+impl Cassette {
+    /// Starts a new recording at `path`, replacing any existing cassette there.
+    pub fn record<P: AsRef<Path>>(path: P) -> Self {
+        Self::with_state(path.as_ref(), CassetteMode::Record, State::default())
+    }
+
+    /// Loads a previously recorded cassette for offline replay.
+    pub fn replay<P: AsRef<Path>>(path: P) -> Result<Self, VyperError> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path).map_err(|e| {
+            VyperError::CassetteError(format!("failed to read {}: {}", path.display(), e))
+        })?;
+        let file: CassetteFile = serde_json::from_str(&contents)?;
+        let state = State {
+            used: vec![false; file.interactions.len()],
+            interactions: file.interactions,
+        };
+        Ok(Self::with_state(path, CassetteMode::Replay, state))
+    }
+
+    fn with_state(path: &Path, mode: CassetteMode, state: State) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                path: path.to_path_buf(),
+                mode,
+                state: Mutex::new(state),
+                write_lock: tokio::sync::Mutex::new(()),
+            }),
+        }
+    }
+
+    pub fn mode(&self) -> CassetteMode {
+        self.inner.mode
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.inner.path
+    }
+
+    pub fn interactions(&self) -> Vec<Interaction> {
+        self.lock().interactions.clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.inner.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub(crate) fn play(&self, request: &reqwest::Request) -> Result<RawResponse, VyperError> {
+        let recorded = RecordedRequest::from_request(request);
+        let mut state = self.lock();
+        let State { interactions, used } = &mut *state;
+
+        let index = interactions
+            .iter()
+            .zip(used.iter())
+            .position(|(interaction, used)| !used && interaction.request.matches(&recorded))
+            .ok_or_else(|| {
+                VyperError::CassetteError(format!(
+                    "no recorded interaction for {} {}{} in {}",
+                    recorded.method,
+                    recorded.path,
+                    recorded.query_string(),
+                    self.inner.path.display()
+                ))
+            })?;
+        used[index] = true;
+        interactions[index].response.to_raw()
+    }
+
+    pub(crate) async fn record_interaction(&self, request: RecordedRequest, response: &RawResponse) -> Result<(), VyperError> {
+        {
+            let mut state = self.lock();
+            state.interactions.push(Interaction {
+                request,
+                response: RecordedResponse::from_raw(response),
+            });
+            state.used.push(true);
+        }
+
+        // Serialise writers and snapshot under the write lock so a slower
+        // writer can never replace the file with an older set of interactions.
+        let _write = self.inner.write_lock.lock().await;
+        let file = CassetteFile {
+            interactions: self.interactions(),
+        };
+        let contents = serde_json::to_string_pretty(&file)?;
+        let path = &self.inner.path;
+        let write = async {
+            if let Some(parent) = path.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            tokio::fs::write(path, contents).await
+        };
+        write.await.map_err(|e| {
+            VyperError::CassetteError(format!("failed to write {}: {}", path.display(), e))
+        })
+    }
+}
+
+fn record_header(name: &HeaderName, value: &HeaderValue) -> (String, String) {
+    let value = if REDACTED_HEADERS.contains(&name.as_str()) {
+        REDACTED.to_string()
+    } else {
+        String::from_utf8_lossy(value.as_bytes()).into_owned()
+    };
+    (name.as_str().to_string(), value)
+}
+
+impl RecordedRequest {
+    pub(crate) fn from_request(request: &reqwest::Request) -> Self {
+        let url = request.url();
+        let mut query: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+        query.sort();
+
+        let headers = request
+            .headers()
+            .iter()
+            .map(|(name, value)| record_header(name, value))
+            .collect();
+
+        Self {
+            method: request.method().as_str().to_string(),
+            path: url.path().to_string(),
+            query,
+            headers,
+        }
+    }
+
+    fn matches(&self, other: &RecordedRequest) -> bool {
+        let mut query = self.query.clone();
+        query.sort();
+        self.method.eq_ignore_ascii_case(&other.method) && self.path == other.path && query == other.query
+    }
+
+    fn query_string(&self) -> String {
+        if self.query.is_empty() {
+            return String::new();
+        }
+        let pairs: Vec<String> = self.query.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        format!("?{}", pairs.join("&"))
+    }
+}
+
+impl RecordedResponse {
+    fn from_raw(response: &RawResponse) -> Self {
+        Self {
+            status: response.status.as_u16(),
+            headers: response.headers.iter().map(|(name, value)| record_header(name, value)).collect(),
+            body: response.body.clone(),
+        }
+    }
+
+    fn to_raw(&self) -> Result<RawResponse, VyperError> {
+        let status = StatusCode::from_u16(self.status)
+            .map_err(|e| VyperError::CassetteError(format!("invalid recorded status {}: {}", self.status, e)))?;
+
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.headers {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|e| VyperError::CassetteError(format!("invalid recorded header {:?}: {}", name, e)))?;
+            let value = HeaderValue::from_str(value)
+                .map_err(|e| VyperError::CassetteError(format!("invalid recorded header value {:?}: {}", value, e)))?;
+            headers.append(name, value);
+        }
+
+        Ok(RawResponse {
+            status,
+            headers,
+            body: self.body.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::VyperClient;
+    use crate::retry::RetryPolicy;
+    use crate::types::ChainId;
+    use mockito::{mock, server_url};
+
+    fn cassette_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("vyper-cassette-{}-{}.json", name, std::process::id()))
+    }
+
+    #[tokio::test]
+    async fn test_record_then_replay() {
+        let path = cassette_path("record-replay");
+        let recorder = VyperClient::builder()
+            .api_key("secret-key")
+            .base_url(server_url())
+            .cassette(Cassette::record(&path))
+            .build()
+            .unwrap();
+
+        let _m = mock("GET", "/api/v1/token/symbol")
+            .match_query(mockito::Matcher::UrlEncoded("tokenMint".into(), "cassette-mint".into()))
+            .with_status(200)
+            .with_header("X-Request-Id", "recorded-id")
+            .with_header("Set-Cookie", "session=secret-session")
+            .with_body(r#"{"status":"success","message":"ok","data":{"symbol":"REC"}}"#)
+            .create();
+
+        let symbol = recorder.get_token_symbol(ChainId::Solana, "cassette-mint").await.unwrap();
+        assert_eq!(symbol.symbol, "REC");
+
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(!contents.contains("secret-key"));
+        assert!(!contents.contains("secret-session"));
+        let recorded = Cassette::replay(&path).unwrap().interactions();
+        assert_eq!(recorded[0].request.headers["x-api-key"], REDACTED);
+        assert_eq!(recorded[0].response.headers["set-cookie"], REDACTED);
+
+        let player = VyperClient::builder()
+            .api_key("another-key")
+            .base_url("http://127.0.0.1:9")
+            .retry_policy(RetryPolicy::none())
+            .cassette(Cassette::replay(&path).unwrap())
+            .build()
+            .unwrap();
+
+        let response = player.get_token_symbol_with_meta(ChainId::Solana, "cassette-mint").await.unwrap();
+        assert_eq!(response.data.symbol, "REC");
+        assert_eq!(response.request_id.as_deref(), Some("recorded-id"));
+
+        let result = player.get_token_symbol(ChainId::Solana, "cassette-mint").await;
+        assert!(matches!(result, Err(VyperError::CassetteError(_))));
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_replay_fails_on_unmatched_request() {
+        let path = cassette_path("unmatched");
+        fs::write(&path, r#"{"interactions": []}"#).unwrap();
+
+        let player = VyperClient::builder()
+            .api_key("key")
+            .retry_policy(RetryPolicy::none())
+            .cassette(Cassette::replay(&path).unwrap())
+            .build()
+            .unwrap();
+
+        let result = player.get_chain_ids().await;
+        match result {
+            Err(VyperError::CassetteError(message)) => assert!(message.contains("GET /api/v1/chain/ids")),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_query_order_does_not_matter() {
+        let recorded = RecordedRequest {
+            method: "GET".to_string(),
+            path: "/api/v1/token/ath".to_string(),
+            query: vec![("marketID".to_string(), "m".to_string()), ("chainID".to_string(), "900".to_string())],
+            headers: BTreeMap::new(),
+        };
+        let incoming = RecordedRequest {
+            query: vec![("chainID".to_string(), "900".to_string()), ("marketID".to_string(), "m".to_string())],
+            ..recorded.clone()
+        };
+        assert!(recorded.matches(&incoming));
+    }
+}
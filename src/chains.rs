@@ -0,0 +1,119 @@
+use std::future::Future;
+use futures_util::future::join_all;
+use crate::client::VyperClient;
+use crate::errors::VyperError;
+use crate::types::ChainId;
+
+/// Outcome of [`VyperClient::fan_out_chains`], split by success and ordered
+/// by chain id.
+pub(crate) struct ChainResults<T> {
+    pub succeeded: Vec<(ChainId, T)>,
+    pub failures: Vec<(ChainId, VyperError)>,
+}
+
+impl VyperClient {
+    /// Runs `request` once for every chain in `chains`, concurrently. An empty
+    /// `chains` means every chain reported by
+    /// [`get_chain_ids`](VyperClient::get_chain_ids); duplicates are requested
+    /// once.
+    ///
+    /// Fails when the chain list cannot be fetched, or with the first chain's
+    /// error when every chain failed.
+    pub(crate) async fn fan_out_chains<T, F, Fut>(&self, chains: &[ChainId], request: F) -> Result<ChainResults<T>, VyperError>
+    where
+        F: Fn(ChainId) -> Fut,
+        Fut: Future<Output = Result<T, VyperError>>,
+    {
+        let mut chains: Vec<ChainId> = if chains.is_empty() {
+            self.get_chain_ids().await?.into_values().collect()
+        } else {
+            chains.to_vec()
+        };
+        chains.sort_by_key(|chain_id| chain_id.id());
+        chains.dedup();
+
+        let responses = join_all(chains.into_iter().map(|chain_id| {
+            let response = request(chain_id);
+            async move { (chain_id, response.await) }
+        }))
+        .await;
+
+        let mut results = ChainResults {
+            succeeded: Vec::new(),
+            failures: Vec::new(),
+        };
+        for (chain_id, response) in responses {
+            match response {
+                Ok(value) => results.succeeded.push((chain_id, value)),
+                Err(e) => results.failures.push((chain_id, e)),
+            }
+        }
+        if results.succeeded.is_empty() && !results.failures.is_empty() {
+            return Err(results.failures.remove(0).1);
+        }
+        Ok(results)
+    }
+}
+
+/// Client against the mockito server without retries, shared by the tests of
+/// the multi-chain helpers.
+#[cfg(test)]
+pub(crate) fn setup_client() -> VyperClient {
+    VyperClient::builder()
+        .api_key("test_api_key")
+        .base_url(mockito::server_url())
+        .retry_policy(crate::retry::RetryPolicy::none())
+        .build()
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    fn server_error(chain_id: ChainId) -> VyperError {
+        VyperError::ServerError {
+            message: format!("chain {} unavailable", chain_id.id()),
+            status: 503,
+            body: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_dedups_and_orders_chains() {
+        let requested = Mutex::new(Vec::new());
+        let results = setup_client()
+            .fan_out_chains(&[ChainId::Base, ChainId::Solana, ChainId::Tron, ChainId::Base], |chain_id| {
+                requested.lock().unwrap().push(chain_id);
+                async move {
+                    match chain_id {
+                        ChainId::Tron => Err(server_error(chain_id)),
+                        _ => Ok(chain_id.id()),
+                    }
+                }
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(requested.lock().unwrap().len(), 3);
+        let succeeded: Vec<ChainId> = results.succeeded.iter().map(|(chain_id, _)| *chain_id).collect();
+        assert_eq!(succeeded, vec![ChainId::Solana, ChainId::Base]);
+        assert_eq!(results.failures.len(), 1);
+        assert_eq!(results.failures[0].0, ChainId::Tron);
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_returns_first_error_when_every_chain_fails() {
+        let result = setup_client()
+            .fan_out_chains(&[ChainId::Base, ChainId::Tron], |chain_id| async move {
+                Err::<(), _>(server_error(chain_id))
+            })
+            .await;
+
+        match result {
+            Err(VyperError::ServerError { message, .. }) => assert_eq!(message, format!("chain {} unavailable", ChainId::Tron.id())),
+            _ => panic!("expected the first chain's error"),
+        }
+    }
+}
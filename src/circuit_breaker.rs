@@ -0,0 +1,311 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::time::Instant;
+use crate::errors::VyperError;
+
+/// State of a [`CircuitBreaker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests flow normally and their outcomes are tracked.
+    Closed,
+    /// Requests fail immediately with [`VyperError::CircuitOpen`].
+    Open,
+    /// The cooldown has elapsed; a single probe request is let through to
+    /// decide whether to close or re-open the circuit.
+    HalfOpen,
+}
+
+/// Point-in-time view of a [`CircuitBreaker`], e.g. for exporting to a dashboard.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CircuitMetrics {
+    pub state: CircuitState,
+    /// Failure rate (0.0 - 1.0) over the outcomes currently in the window.
+    pub failure_rate: f64,
+    /// Number of outcomes currently in the window.
+    pub window_len: usize,
+    /// How many times the circuit has opened since it was created.
+    pub times_opened: u64,
+}
+
+/// Stops sending requests to a degraded API and fails fast instead.
+///
+/// The breaker tracks the outcomes of the last `window_size` requests. Once at
+/// least `minimum_requests` have been seen and the share of server errors
+/// (5xx) and timeouts reaches `failure_rate_threshold`, the circuit opens and
+/// requests fail with [`VyperError::CircuitOpen`] without touching the
+/// network. After `cooldown` one probe request is allowed through: success
+/// closes the circuit, failure opens it for another cooldown.
+///
+/// Clones share the same state, so a breaker attached to a
+/// [`VyperClient`](crate::client::VyperClient) covers all of its clones.
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    failure_rate_threshold: f64,
+    minimum_requests: usize,
+    window_size: usize,
+    cooldown: Duration,
+    inner: Arc<Mutex<Inner>>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    state: CircuitState,
+    /// `true` for a failed request.
+    outcomes: VecDeque<bool>,
+    opened_at: Instant,
+    probe_in_flight: bool,
+    times_opened: u64,
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new(0.5, Duration::from_secs(30))
+    }
+}
+
+impl CircuitBreaker {
+    /// Opens when at least `failure_rate_threshold` (0.0 - 1.0) of recent
+    /// requests failed, and stays open for `cooldown`.
+    pub fn new(failure_rate_threshold: f64, cooldown: Duration) -> Self {
+        Self {
+            failure_rate_threshold: failure_rate_threshold.clamp(0.0, 1.0),
+            minimum_requests: 10,
+            window_size: 20,
+            cooldown,
+            inner: Arc::new(Mutex::new(Inner {
+                state: CircuitState::Closed,
+                outcomes: VecDeque::new(),
+                opened_at: Instant::now(),
+                probe_in_flight: false,
+                times_opened: 0,
+            })),
+        }
+    }
+
+    /// Number of most recent requests the failure rate is computed over.
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size.max(1);
+        self.minimum_requests = self.minimum_requests.min(self.window_size);
+        self
+    }
+
+    /// Requests that must be seen before the circuit can open.
+    pub fn with_minimum_requests(mut self, minimum_requests: usize) -> Self {
+        self.minimum_requests = minimum_requests.clamp(1, self.window_size);
+        self
+    }
+
+    pub fn state(&self) -> CircuitState {
+        let inner = self.lock();
+        self.effective_state(&inner)
+    }
+
+    pub fn metrics(&self) -> CircuitMetrics {
+        let inner = self.lock();
+        CircuitMetrics {
+            state: self.effective_state(&inner),
+            failure_rate: failure_rate(&inner.outcomes),
+            window_len: inner.outcomes.len(),
+            times_opened: inner.times_opened,
+        }
+    }
+
+    /// Whether `error` counts against the circuit. Client errors such as 4xx
+    /// responses mean the API is up and are not counted.
+    pub fn is_failure(error: &VyperError) -> bool {
+        match error {
+            VyperError::ServerError { .. } => true,
+            VyperError::HttpError(e) => e.is_timeout(),
+            _ => false,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn effective_state(&self, inner: &Inner) -> CircuitState {
+        match inner.state {
+            CircuitState::Open if inner.opened_at.elapsed() >= self.cooldown => CircuitState::HalfOpen,
+            state => state,
+        }
+    }
+
+    /// Admits a request, or fails fast while the circuit is open. The returned
+    /// permit must be given the request outcome via [`CircuitPermit::record`].
+    pub(crate) fn try_acquire(&self) -> Result<CircuitPermit<'_>, VyperError> {
+        let mut inner = self.lock();
+        let probe = match self.effective_state(&inner) {
+            CircuitState::Closed => false,
+            CircuitState::HalfOpen if !inner.probe_in_flight => {
+                inner.state = CircuitState::HalfOpen;
+                inner.probe_in_flight = true;
+                true
+            }
+            CircuitState::HalfOpen => {
+                return Err(VyperError::CircuitOpen { retry_after: None });
+            }
+            CircuitState::Open => {
+                let remaining = self.cooldown.saturating_sub(inner.opened_at.elapsed());
+                return Err(VyperError::CircuitOpen { retry_after: Some(remaining) });
+            }
+        };
+        Ok(CircuitPermit {
+            breaker: self,
+            probe,
+            recorded: false,
+        })
+    }
+
+    fn record(&self, failed: bool, probe: bool) {
+        let mut inner = self.lock();
+        if probe {
+            inner.probe_in_flight = false;
+            if failed {
+                self.open(&mut inner);
+            } else {
+                inner.state = CircuitState::Closed;
+                inner.outcomes.clear();
+            }
+            return;
+        }
+
+        if inner.state != CircuitState::Closed {
+            return;
+        }
+        inner.outcomes.push_back(failed);
+        while inner.outcomes.len() > self.window_size {
+            inner.outcomes.pop_front();
+        }
+        if inner.outcomes.len() >= self.minimum_requests
+            && failure_rate(&inner.outcomes) >= self.failure_rate_threshold
+        {
+            self.open(&mut inner);
+        }
+    }
+
+    fn open(&self, inner: &mut Inner) {
+        inner.state = CircuitState::Open;
+        inner.opened_at = Instant::now();
+        inner.outcomes.clear();
+        inner.times_opened += 1;
+    }
+}
+
+fn failure_rate(outcomes: &VecDeque<bool>) -> f64 {
+    if outcomes.is_empty() {
+        return 0.0;
+    }
+    outcomes.iter().filter(|failed| **failed).count() as f64 / outcomes.len() as f64
+}
+
+/// Admission for one request. Dropping it without recording (e.g. when the
+/// request future is cancelled) releases a half-open probe slot so the
+/// circuit cannot get stuck.
+pub(crate) struct CircuitPermit<'a> {
+    breaker: &'a CircuitBreaker,
+    probe: bool,
+    recorded: bool,
+}
+
+impl CircuitPermit<'_> {
+    pub(crate) fn record<T>(mut self, result: &Result<T, VyperError>) {
+        self.recorded = true;
+        let failed = matches!(result, Err(e) if CircuitBreaker::is_failure(e));
+        self.breaker.record(failed, self.probe);
+    }
+}
+
+impl Drop for CircuitPermit<'_> {
+    fn drop(&mut self) {
+        if self.probe && !self.recorded {
+            self.breaker.lock().probe_in_flight = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server_error() -> Result<(), VyperError> {
+        Err(VyperError::ServerError {
+            message: "boom".to_string(),
+            status: 503,
+            body: None,
+        })
+    }
+
+    fn breaker() -> CircuitBreaker {
+        CircuitBreaker::new(0.5, Duration::from_secs(10))
+            .with_window_size(4)
+            .with_minimum_requests(4)
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_opens_at_failure_rate_and_fails_fast() {
+        let breaker = breaker();
+        breaker.try_acquire().unwrap().record(&Ok(()));
+        breaker.try_acquire().unwrap().record(&server_error());
+        breaker.try_acquire().unwrap().record(&Ok(()));
+        assert_eq!(breaker.state(), CircuitState::Closed);
+
+        breaker.try_acquire().unwrap().record(&server_error());
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert_eq!(breaker.metrics().times_opened, 1);
+
+        tokio::time::advance(Duration::from_secs(4)).await;
+        match breaker.try_acquire() {
+            Err(VyperError::CircuitOpen { retry_after }) => assert_eq!(retry_after, Some(Duration::from_secs(6))),
+            _ => panic!("expected the circuit to be open"),
+        };
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_half_open_probe_closes_or_reopens() {
+        let breaker = breaker();
+        for _ in 0..4 {
+            breaker.try_acquire().unwrap().record(&server_error());
+        }
+        tokio::time::advance(Duration::from_secs(10)).await;
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+
+        let probe = breaker.try_acquire().unwrap();
+        assert!(matches!(breaker.try_acquire(), Err(VyperError::CircuitOpen { retry_after: None })));
+        probe.record(&server_error());
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        tokio::time::advance(Duration::from_secs(10)).await;
+        breaker.try_acquire().unwrap().record(&Ok(()));
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert_eq!(breaker.metrics().window_len, 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_dropped_probe_releases_slot() {
+        let breaker = breaker();
+        for _ in 0..4 {
+            breaker.try_acquire().unwrap().record(&server_error());
+        }
+        tokio::time::advance(Duration::from_secs(10)).await;
+
+        drop(breaker.try_acquire().unwrap());
+        assert!(breaker.try_acquire().is_ok());
+    }
+
+    #[test]
+    fn test_client_errors_do_not_count() {
+        let breaker = breaker();
+        let not_found: Result<(), VyperError> = Err(VyperError::ApiError {
+            message: "missing".to_string(),
+            status: 404,
+            body: None,
+        });
+        for _ in 0..8 {
+            breaker.try_acquire().unwrap().record(&not_found);
+        }
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert_eq!(breaker.metrics().failure_rate, 0.0);
+    }
+}
@@ -1,181 +1,1038 @@
+use futures_util::stream::BoxStream;
+use reqwest::header::HeaderMap;
 use reqwest::Client as HttpClient;
 use std::collections::HashMap;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use crate::types::*;
 use crate::errors::*;
+use crate::circuit_breaker::CircuitBreaker;
+use crate::cassette::{Cassette, CassetteMode, RecordedRequest};
+use crate::options::RequestOptions;
+use crate::pagination::{paginate, Page, PaginationOptions};
+use crate::rate_limit::RateLimiter;
+use crate::response::{request_id, RateLimitInfo, RawResponse, Response};
+use crate::retry::RetryPolicy;
 
+const DEFAULT_BASE_URL: &str = "https://api.vyper.trade";
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Clone)]
 pub struct VyperClient {
     base_url: String,
     api_key: String,
     http_client: HttpClient,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<RateLimiter>,
+    circuit_breaker: Option<CircuitBreaker>,
+    cassette: Option<Cassette>,
+    options: RequestOptions,
 }
 
 impl VyperClient {
+    /// Creates a client against the production API with default settings.
+    ///
+    /// Panics if the underlying HTTP client cannot be initialised; use
+    /// [`VyperClient::builder`] to handle that case as an error instead.
     pub fn new(api_key: &str) -> Self {
+        Self::builder()
+            .api_key(api_key)
+            .build()
+            .expect("failed to build default Vyper HTTP client")
+    }
+
+    pub fn builder() -> VyperClientBuilder {
+        VyperClientBuilder::default()
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    pub fn circuit_breaker(&self) -> Option<&CircuitBreaker> {
+        self.circuit_breaker.as_ref()
+    }
+
+    /// Returns a copy of this client whose requests use `options`, replacing
+    /// any options set previously. The copy shares the connection pool, rate
+    /// limiter and circuit breaker with `self`.
+    pub fn with_options(&self, options: RequestOptions) -> Self {
         Self {
-            base_url: "https://api.vyper.trade".to_string(),
-            api_key: api_key.to_string(),
-            http_client: HttpClient::builder()
-                .timeout(Duration::from_secs(10))
-                .build()
-                .unwrap(),
+            options,
+            ..self.clone()
         }
     }
 
-    async fn request<T: serde::de::DeserializeOwned>(
+    pub fn options(&self) -> &RequestOptions {
+        &self.options
+    }
+
+    async fn request_with_meta<T, Q>(
         &self,
         method: reqwest::Method,
         endpoint: &str,
-        params: Option<&[(&str, String)]>,
-    ) -> Result<T, VyperError> {
+        params: Option<&Q>,
+    ) -> Result<Response<T>, VyperError>
+    where
+        T: serde::de::DeserializeOwned,
+        Q: serde::Serialize + ?Sized + Sync,
+    {
+        let attempts = self.attempt_with_retries(&method, endpoint, params);
+        let attempts = async {
+            match self.options.deadline {
+                Some(deadline) => tokio::time::timeout_at(deadline.into(), attempts)
+                    .await
+                    .unwrap_or(Err(VyperError::DeadlineExceeded)),
+                None => attempts.await,
+            }
+        };
+        match &self.options.cancellation_token {
+            Some(token) => tokio::select! {
+                _ = token.cancelled() => Err(VyperError::Cancelled),
+                result = attempts => result,
+            },
+            None => attempts.await,
+        }
+    }
+
+    async fn attempt_with_retries<T, Q>(
+        &self,
+        method: &reqwest::Method,
+        endpoint: &str,
+        params: Option<&Q>,
+    ) -> Result<Response<T>, VyperError>
+    where
+        T: serde::de::DeserializeOwned,
+        Q: serde::Serialize + ?Sized + Sync,
+    {
+        let retry_policy = self.options.retry_policy.as_ref().unwrap_or(&self.retry_policy);
+        let mut attempt = 1;
+        loop {
+            let permit = match &self.circuit_breaker {
+                Some(breaker) => Some(breaker.try_acquire()?),
+                None => None,
+            };
+            if let Some(limiter) = &self.rate_limiter {
+                limiter.acquire(endpoint).await;
+            }
+            let result = self.send_request(method.clone(), endpoint, params).await;
+            if let Some(permit) = permit {
+                permit.record(&result);
+            }
+            match result {
+                Ok(response) => {
+                    return Ok(Response {
+                        attempts: attempt,
+                        ..response
+                    })
+                }
+                Err(err) => match retry_policy.next_delay(attempt, &err) {
+                    Some(delay) if !self.past_deadline_after(delay) => {
+                        tokio::time::sleep(delay).await;
+                        attempt += 1;
+                    }
+                    _ => return Err(err),
+                },
+            }
+        }
+    }
+
+    fn past_deadline_after(&self, delay: Duration) -> bool {
+        self.options
+            .deadline
+            .is_some_and(|deadline| Instant::now() + delay >= deadline)
+    }
+
+    async fn send_request<T, Q>(
+        &self,
+        method: reqwest::Method,
+        endpoint: &str,
+        params: Option<&Q>,
+    ) -> Result<Response<T>, VyperError>
+    where
+        T: serde::de::DeserializeOwned,
+        Q: serde::Serialize + ?Sized + Sync,
+    {
         let url = format!("{}{}", self.base_url, endpoint);
         let mut request = self.http_client.request(method, &url);
 
         request = request.header("X-API-Key", &self.api_key);
+        if !self.options.headers.is_empty() {
+            request = request.headers(self.options.headers.clone());
+        }
+        if let Some(timeout) = self.options.timeout {
+            request = request.timeout(timeout);
+        }
 
         if let Some(query_params) = params {
             request = request.query(query_params);
         }
 
-        let response = request.send().await?;
-        let status = response.status();
-        let headers = response.headers().clone();
-        let body = response.text().await?;
+        let request = request.build()?;
+        let started = Instant::now();
+        let RawResponse { status, headers, body } = match &self.cassette {
+            Some(cassette) if cassette.mode() == CassetteMode::Replay => cassette.play(&request)?,
+            Some(cassette) => {
+                let recorded = RecordedRequest::from_request(&request);
+                let response = self.execute(request).await?;
+                cassette.record_interaction(recorded, &response).await?;
+                response
+            }
+            None => self.execute(request).await?,
+        };
+        let latency = started.elapsed();
 
         if status.is_success() {
             let api_response: APIResponse<T> = serde_json::from_str(&body)?;
-            Ok(api_response.data)
+            Ok(Response {
+                data: api_response.data,
+                status,
+                api_status: api_response.status,
+                message: api_response.message,
+                rate_limit: RateLimitInfo::from_headers(&headers),
+                request_id: request_id(&headers),
+                latency,
+                attempts: 1,
+                headers,
+            })
         } else {
-            let error_response: APIResponse<serde_json::Value> = serde_json::from_str(&body)?;
-            match status.as_u16() {
-                401 => Err(VyperError::AuthenticationError(error_response.message)),
-                429 => Err(VyperError::RateLimitError {
-                    message: error_response.message,
-                    retry_after: headers.get("Retry-After")
-                        .and_then(|h| h.to_str().ok())
-                        .and_then(|s| s.parse().ok()),
-                }),
-                500..=599 => Err(VyperError::ServerError(error_response.message)),
-                _ => Err(VyperError::ApiError(error_response.message, status.as_u16())),
-            }
+            Err(error_from_response(status, &headers, &body))
         }
     }
 
-    pub async fn get_chain_ids(&self) -> Result<std::collections::HashMap<String, i32>, VyperError> {
-        self.request(reqwest::Method::GET, "/api/v1/chain/ids", None).await
+    async fn execute(&self, request: reqwest::Request) -> Result<RawResponse, VyperError> {
+        let response = self.http_client.execute(request).await?;
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.text().await?;
+        Ok(RawResponse { status, headers, body })
+    }
+
+    pub async fn get_chain_ids(&self) -> Result<HashMap<String, ChainId>, VyperError> {
+        self.get_chain_ids_with_meta().await.map(Response::into_data)
     }
 
-    pub async fn get_token_ath(&self, chain_id: i32, market_id: &str) -> Result<TokenATH, VyperError> {
+    pub async fn get_chain_ids_with_meta(&self) -> Result<Response<HashMap<String, ChainId>>, VyperError> {
+        self.request_with_meta(reqwest::Method::GET, "/api/v1/chain/ids", None::<&()>).await
+    }
+
+    pub async fn get_token_ath(&self, chain_id: ChainId, market_id: &str) -> Result<TokenATH, VyperError> {
+        self.get_token_ath_with_meta(chain_id, market_id).await.map(Response::into_data)
+    }
+
+    pub async fn get_token_ath_with_meta(&self, chain_id: ChainId, market_id: &str) -> Result<Response<TokenATH>, VyperError> {
         let params = vec![
-            ("chainID", chain_id.to_string()),
+            ("chainID", chain_id.id().to_string()),
             ("marketID", market_id.to_string()),
         ];
-        self.request(reqwest::Method::GET, "/api/v1/token/ath", Some(&params)).await
+        self.request_with_meta(reqwest::Method::GET, "/api/v1/token/ath", Some(&params)).await
+    }
+
+    pub async fn get_token_market(&self, market_id: &str, chain_id: ChainId, interval: Interval) -> Result<TokenPair, VyperError> {
+        self.get_token_market_with_meta(market_id, chain_id, interval).await.map(Response::into_data)
     }
 
-    pub async fn get_token_market(&self, market_id: &str, chain_id: i32, interval: &str) -> Result<TokenPair, VyperError> {
+    pub async fn get_token_market_with_meta(&self, market_id: &str, chain_id: ChainId, interval: Interval) -> Result<Response<TokenPair>, VyperError> {
         let params = vec![
-            ("chainID", chain_id.to_string()),
+            ("chainID", chain_id.id().to_string()),
             ("interval", interval.to_string()),
         ];
-        self.request(reqwest::Method::GET, &format!("/api/v1/token/market/{}", market_id), Some(&params)).await
+        self.request_with_meta(reqwest::Method::GET, &format!("/api/v1/token/market/{}", market_id), Some(&params)).await
     }
 
-    pub async fn get_token_holders(&self, market_id: &str, chain_id: i32) -> Result<(Vec<TokenHolder>, i32), VyperError> {
-        let params = vec![
+    /// Returns the first page of holders, using the server's default page size.
+    pub async fn get_token_holders(&self, market_id: &str, chain_id: ChainId) -> Result<TokenHolders, VyperError> {
+        self.get_token_holders_page(market_id, chain_id, TokenHoldersParams::default()).await
+    }
+
+    pub async fn get_token_holders_with_meta(&self, market_id: &str, chain_id: ChainId) -> Result<Response<TokenHolders>, VyperError> {
+        self.get_token_holders_page_with_meta(market_id, chain_id, TokenHoldersParams::default()).await
+    }
+
+    pub async fn get_token_holders_page(&self, market_id: &str, chain_id: ChainId, params: TokenHoldersParams) -> Result<TokenHolders, VyperError> {
+        self.get_token_holders_page_with_meta(market_id, chain_id, params).await.map(Response::into_data)
+    }
+
+    pub async fn get_token_holders_page_with_meta(&self, market_id: &str, chain_id: ChainId, params: TokenHoldersParams) -> Result<Response<TokenHolders>, VyperError> {
+        params.validate()?;
+        let mut query = vec![
             ("marketID", market_id.to_string()),
-            ("chainID", chain_id.to_string()),
+            ("chainID", chain_id.id().to_string()),
         ];
-        let result: HashMap<String, serde_json::Value> = self.request(reqwest::Method::GET, "/api/v1/token/holders", Some(&params)).await?;
-        
-        let holders: Vec<TokenHolder> = serde_json::from_value(result["holders"].clone())?;
-        let total_holders: i32 = result["total_holders"].as_i64().unwrap() as i32;
+        if let Some(page) = params.page {
+            query.push(("page", page.to_string()));
+        }
+        if let Some(limit) = params.limit {
+            query.push(("limit", limit.to_string()));
+        }
+        self.request_with_meta(reqwest::Method::GET, "/api/v1/token/holders", Some(&query)).await
+    }
 
-        Ok((holders, total_holders))
+    pub async fn get_token_markets(&self, token_mint: &str, chain_id: ChainId) -> Result<Vec<TokenMarket>, VyperError> {
+        self.get_token_markets_with_meta(token_mint, chain_id).await.map(Response::into_data)
     }
 
-    pub async fn get_token_markets(&self, token_mint: &str, chain_id: i32) -> Result<Vec<TokenMarket>, VyperError> {
+    pub async fn get_token_markets_with_meta(&self, token_mint: &str, chain_id: ChainId) -> Result<Response<Vec<TokenMarket>>, VyperError> {
         let params = vec![
             ("tokenMint", token_mint.to_string()),
-            ("chainID", chain_id.to_string()),
+            ("chainID", chain_id.id().to_string()),
         ];
-        self.request(reqwest::Method::GET, "/api/v1/token/markets", Some(&params)).await
+        self.request_with_meta(reqwest::Method::GET, "/api/v1/token/markets", Some(&params)).await
+    }
+
+    pub async fn get_wallet_holdings(&self, wallet_address: &str, chain_id: ChainId) -> Result<Vec<WalletHolding>, VyperError> {
+        self.get_wallet_holdings_with_meta(wallet_address, chain_id).await.map(Response::into_data)
     }
 
-    pub async fn get_wallet_holdings(&self, wallet_address: &str, chain_id: i32) -> Result<Vec<WalletHolding>, VyperError> {
+    pub async fn get_wallet_holdings_with_meta(&self, wallet_address: &str, chain_id: ChainId) -> Result<Response<Vec<WalletHolding>>, VyperError> {
         let params = vec![
             ("walletAddress", wallet_address.to_string()),
-            ("chainID", chain_id.to_string()),
+            ("chainID", chain_id.id().to_string()),
         ];
-        self.request(reqwest::Method::GET, "/api/v1/wallet/holdings", Some(&params)).await
+        self.request_with_meta(reqwest::Method::GET, "/api/v1/wallet/holdings", Some(&params)).await
     }
 
-    pub async fn get_wallet_aggregated_pnl(&self, wallet_address: &str, chain_id: i32) -> Result<WalletAggregatedPnL, VyperError> {
+    pub async fn get_wallet_aggregated_pnl(&self, wallet_address: &str, chain_id: ChainId) -> Result<WalletAggregatedPnL, VyperError> {
+        self.get_wallet_aggregated_pnl_with_meta(wallet_address, chain_id).await.map(Response::into_data)
+    }
+
+    pub async fn get_wallet_aggregated_pnl_with_meta(&self, wallet_address: &str, chain_id: ChainId) -> Result<Response<WalletAggregatedPnL>, VyperError> {
         let params = vec![
             ("walletAddress", wallet_address.to_string()),
-            ("chainID", chain_id.to_string()),
+            ("chainID", chain_id.id().to_string()),
         ];
-        self.request(reqwest::Method::GET, "/api/v1/wallet/aggregated-pnl", Some(&params)).await
+        self.request_with_meta(reqwest::Method::GET, "/api/v1/wallet/aggregated-pnl", Some(&params)).await
+    }
+
+    pub async fn get_wallet_pnl(&self, wallet_address: &str, market_id: &str, chain_id: ChainId) -> Result<WalletPnL, VyperError> {
+        self.get_wallet_pnl_with_meta(wallet_address, market_id, chain_id).await.map(Response::into_data)
     }
 
-    pub async fn get_wallet_pnl(&self, wallet_address: &str, market_id: &str, chain_id: i32) -> Result<WalletPnL, VyperError> {
+    pub async fn get_wallet_pnl_with_meta(&self, wallet_address: &str, market_id: &str, chain_id: ChainId) -> Result<Response<WalletPnL>, VyperError> {
         let params = vec![
             ("walletAddress", wallet_address.to_string()),
             ("marketID", market_id.to_string()),
-            ("chainID", chain_id.to_string()),
+            ("chainID", chain_id.id().to_string()),
         ];
-        self.request(reqwest::Method::GET, "/api/v1/wallet/pnl", Some(&params)).await
+        self.request_with_meta(reqwest::Method::GET, "/api/v1/wallet/pnl", Some(&params)).await
+    }
+
+    pub async fn get_token_metadata(&self, chain_id: ChainId, token_mint: &str) -> Result<TokenMetadata, VyperError> {
+        self.get_token_metadata_with_meta(chain_id, token_mint).await.map(Response::into_data)
     }
 
-    pub async fn get_token_metadata(&self, chain_id: i32, token_mint: &str) -> Result<TokenMetadata, VyperError> {
+    pub async fn get_token_metadata_with_meta(&self, chain_id: ChainId, token_mint: &str) -> Result<Response<TokenMetadata>, VyperError> {
         let params = vec![
-            ("chainID", chain_id.to_string()),
+            ("chainID", chain_id.id().to_string()),
             ("tokenMint", token_mint.to_string()),
         ];
-        self.request(reqwest::Method::GET, "/api/v1/token/metadata", Some(&params)).await
+        self.request_with_meta(reqwest::Method::GET, "/api/v1/token/metadata", Some(&params)).await
     }
 
-    pub async fn get_token_symbol(&self, chain_id: i32, token_mint: &str) -> Result<TokenSymbol, VyperError> {
+    pub async fn get_token_symbol(&self, chain_id: ChainId, token_mint: &str) -> Result<TokenSymbol, VyperError> {
+        self.get_token_symbol_with_meta(chain_id, token_mint).await.map(Response::into_data)
+    }
+
+    pub async fn get_token_symbol_with_meta(&self, chain_id: ChainId, token_mint: &str) -> Result<Response<TokenSymbol>, VyperError> {
         let params = vec![
-            ("chainID", chain_id.to_string()),
+            ("chainID", chain_id.id().to_string()),
             ("tokenMint", token_mint.to_string()),
         ];
-        self.request(reqwest::Method::GET, "/api/v1/token/symbol", Some(&params)).await
+        self.request_with_meta(reqwest::Method::GET, "/api/v1/token/symbol", Some(&params)).await
     }
 
-    pub async fn get_top_traders(&self, market_id: &str, chain_id: i32) -> Result<Vec<TopTrader>, VyperError> {
+    pub async fn get_top_traders(&self, market_id: &str, chain_id: ChainId) -> Result<Vec<TopTrader>, VyperError> {
+        self.get_top_traders_with_meta(market_id, chain_id).await.map(Response::into_data)
+    }
+
+    pub async fn get_top_traders_with_meta(&self, market_id: &str, chain_id: ChainId) -> Result<Response<Vec<TopTrader>>, VyperError> {
         let params = vec![
             ("marketID", market_id.to_string()),
-            ("chainID", chain_id.to_string()),
+            ("chainID", chain_id.id().to_string()),
         ];
-        self.request(reqwest::Method::GET, "/api/v1/token/top-traders", Some(&params)).await
+        self.request_with_meta(reqwest::Method::GET, "/api/v1/token/top-traders", Some(&params)).await
     }
 
-    pub async fn search_tokens(&self, criteria: &str, chain_id: Option<i32>) -> Result<Vec<TokenSearchResult>, VyperError> {
+    pub async fn search_tokens(&self, criteria: &str, chain_id: Option<ChainId>) -> Result<Vec<TokenSearchResult>, VyperError> {
+        self.search_tokens_with_meta(criteria, chain_id).await.map(Response::into_data)
+    }
+
+    pub async fn search_tokens_with_meta(&self, criteria: &str, chain_id: Option<ChainId>) -> Result<Response<Vec<TokenSearchResult>>, VyperError> {
         let mut params = vec![("criteria", criteria.to_string())];
         if let Some(id) = chain_id {
-            params.push(("chainID", id.to_string()));
+            params.push(("chainID", id.id().to_string()));
         }
-        self.request(reqwest::Method::GET, "/api/v1/token/search", Some(&params)).await
+        self.request_with_meta(reqwest::Method::GET, "/api/v1/token/search", Some(&params)).await
     }
 
     pub async fn get_token_pairs(&self, params: TokenPairsParams) -> Result<TokenPairs, VyperError> {
-        let query_params: Vec<(&str, String)> = params.into_iter().collect();
-        self.request(reqwest::Method::GET, "/api/v1/token/pairs", Some(&query_params)).await
+        self.get_token_pairs_with_meta(params).await.map(Response::into_data)
+    }
+
+    pub async fn get_token_pairs_with_meta(&self, params: TokenPairsParams) -> Result<Response<TokenPairs>, VyperError> {
+        params.validate()?;
+        self.request_with_meta(reqwest::Method::GET, "/api/v1/token/pairs", Some(&params)).await
+    }
+
+    /// Streams every pair matching `params`, fetching pages until the server
+    /// reports `has_next == false`. Starts at `params.page`, or page 1.
+    pub fn token_pairs_stream(&self, params: TokenPairsParams) -> BoxStream<'static, Result<TokenPair, VyperError>> {
+        self.token_pairs_stream_with(params, PaginationOptions::default())
+    }
+
+    pub fn token_pairs_stream_with(
+        &self,
+        params: TokenPairsParams,
+        options: PaginationOptions,
+    ) -> BoxStream<'static, Result<TokenPair, VyperError>> {
+        let client = self.clone();
+        let first_page = params.page.unwrap_or(1);
+        let fetch = move |page| {
+            let client = client.clone();
+            let params = TokenPairsParams {
+                page: Some(page),
+                ..params.clone()
+            };
+            async move {
+                let pairs = client.get_token_pairs(params).await?;
+                Ok(Page {
+                    items: pairs.pairs,
+                    has_next: pairs.has_next,
+                })
+            }
+        };
+        paginate(fetch, first_page, options)
+    }
+}
+
+impl VyperClient {
+    /// Streams every holder of a market, one page at a time, stopping at the
+    /// first empty page.
+    pub fn token_holders_stream(&self, market_id: &str, chain_id: ChainId) -> BoxStream<'static, Result<TokenHolder, VyperError>> {
+        self.token_holders_stream_with(market_id, chain_id, TokenHoldersParams::default(), PaginationOptions::default())
+    }
+
+    /// Like [`token_holders_stream`](VyperClient::token_holders_stream), starting
+    /// at `params.page` (or page 1) with `params.limit` holders per page. When
+    /// `limit` is set, a short page or reaching `total_holders` also ends the
+    /// stream.
+    pub fn token_holders_stream_with(
+        &self,
+        market_id: &str,
+        chain_id: ChainId,
+        params: TokenHoldersParams,
+        options: PaginationOptions,
+    ) -> BoxStream<'static, Result<TokenHolder, VyperError>> {
+        let client = self.clone();
+        let market_id = market_id.to_string();
+        let first_page = params.page.unwrap_or(1);
+        let fetch = move |page: i32| {
+            let client = client.clone();
+            let market_id = market_id.clone();
+            let params = TokenHoldersParams {
+                page: Some(page),
+                ..params
+            };
+            async move {
+                let holders = client.get_token_holders_page(&market_id, chain_id, params).await?;
+                let received = holders.holders.len() as i64;
+                // Without a limit the server's page size is unknown, so only an
+                // empty page marks the end.
+                let has_next = match params.limit {
+                    Some(limit) => {
+                        let seen = i64::from(page - 1) * i64::from(limit) + received;
+                        received >= i64::from(limit) && seen < i64::from(holders.total_holders)
+                    }
+                    None => received > 0,
+                };
+                Ok(Page {
+                    items: holders.holders,
+                    has_next,
+                })
+            }
+        };
+        paginate(fetch, first_page, options)
+    }
+}
+
+const MAX_ERROR_BODY_CHARS: usize = 512;
+
+/// Maps a non-success response to a [`VyperError`].
+///
+/// The body is not required to be an [`APIResponse`]: load balancers and
+/// proxies return HTML or empty bodies, in which case the message falls back
+/// to the body text or the status reason.
+fn error_from_response(status: reqwest::StatusCode, headers: &HeaderMap, body: &str) -> VyperError {
+    let trimmed = body.trim();
+    let raw_body = if trimmed.is_empty() {
+        None
+    } else if trimmed.chars().count() > MAX_ERROR_BODY_CHARS {
+        Some(format!("{}...", trimmed.chars().take(MAX_ERROR_BODY_CHARS).collect::<String>()))
+    } else {
+        Some(trimmed.to_string())
+    };
+
+    let json_message = serde_json::from_str::<serde_json::Value>(trimmed).ok().and_then(|value| {
+        ["message", "error"]
+            .iter()
+            .find_map(|key| value.get(key).and_then(|m| m.as_str()).map(str::to_string))
+            .filter(|m| !m.is_empty())
+    });
+
+    let message = json_message
+        .or_else(|| raw_body.clone())
+        .unwrap_or_else(|| match status.canonical_reason() {
+            Some(reason) => format!("{} {}", status.as_u16(), reason),
+            None => format!("HTTP {}", status.as_u16()),
+        });
+
+    match status.as_u16() {
+        401 => VyperError::AuthenticationError(message),
+        429 => VyperError::RateLimitError {
+            message,
+            retry_after: headers
+                .get("Retry-After")
+                .and_then(|h| h.to_str().ok())
+                .and_then(|s| s.trim().parse().ok()),
+        },
+        code @ 500..=599 => VyperError::ServerError {
+            message,
+            status: code,
+            body: raw_body,
+        },
+        code => VyperError::ApiError {
+            message,
+            status: code,
+            body: raw_body,
+        },
+    }
+}
+
+/// Builder for [`VyperClient`], obtained via [`VyperClient::builder`].
+///
+/// When an existing `reqwest::Client` is supplied with
+/// [`http_client`](VyperClientBuilder::http_client), the transport options
+/// (timeouts, user agent, default headers and proxy) must be configured on
+/// that client instead; combining the two is reported as an error by
+/// [`build`](VyperClientBuilder::build).
+#[derive(Default)]
+pub struct VyperClientBuilder {
+    api_key: Option<String>,
+    base_url: Option<String>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    user_agent: Option<String>,
+    default_headers: Option<HeaderMap>,
+    proxy: Option<reqwest::Proxy>,
+    http_client: Option<HttpClient>,
+    retry_policy: Option<RetryPolicy>,
+    rate_limiter: Option<RateLimiter>,
+    circuit_breaker: Option<CircuitBreaker>,
+    cassette: Option<Cassette>,
+}
+
+impl VyperClientBuilder {
+    pub fn api_key<S: Into<String>>(mut self, api_key: S) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Overrides the API root, e.g. a staging environment or a local mock server.
+    pub fn base_url<S: Into<String>>(mut self, base_url: S) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Total request timeout. Defaults to 10 seconds.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    pub fn user_agent<S: Into<String>>(mut self, user_agent: S) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Headers sent with every request, in addition to `X-API-Key`.
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.default_headers = Some(headers);
+        self
+    }
+
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Uses an externally configured `reqwest::Client` instead of building one.
+    pub fn http_client(mut self, http_client: HttpClient) -> Self {
+        self.http_client = Some(http_client);
+        self
+    }
+
+    /// Retry behaviour for failed requests. Defaults to [`RetryPolicy::default`];
+    /// pass [`RetryPolicy::none`] to disable retries.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Paces outgoing requests, including retries, through a shared token bucket.
+    pub fn rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Fails requests fast while the API is degraded. See [`CircuitBreaker`].
+    pub fn circuit_breaker(mut self, circuit_breaker: CircuitBreaker) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
+    }
+
+    /// Records requests to, or replays them from, a cassette file. See [`Cassette`].
+    pub fn cassette(mut self, cassette: Cassette) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
+    pub fn build(self) -> Result<VyperClient, VyperError> {
+        let api_key = self
+            .api_key
+            .ok_or_else(|| VyperError::ConfigurationError("API key is required".to_string()))?;
+
+        let base_url = self.base_url.unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+        url::Url::parse(&base_url).map_err(|e| {
+            VyperError::ConfigurationError(format!("invalid base URL {:?}: {}", base_url, e))
+        })?;
+        let base_url = base_url.trim_end_matches('/').to_string();
+
+        let http_client = match self.http_client {
+            Some(http_client) => {
+                if self.timeout.is_some()
+                    || self.connect_timeout.is_some()
+                    || self.user_agent.is_some()
+                    || self.default_headers.is_some()
+                    || self.proxy.is_some()
+                {
+                    return Err(VyperError::ConfigurationError(
+                        "transport options cannot be combined with a custom http_client".to_string(),
+                    ));
+                }
+                http_client
+            }
+            None => {
+                let mut builder = HttpClient::builder().timeout(self.timeout.unwrap_or(DEFAULT_TIMEOUT));
+                if let Some(connect_timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(connect_timeout);
+                }
+                if let Some(user_agent) = self.user_agent {
+                    builder = builder.user_agent(user_agent);
+                }
+                if let Some(headers) = self.default_headers {
+                    builder = builder.default_headers(headers);
+                }
+                if let Some(proxy) = self.proxy {
+                    builder = builder.proxy(proxy);
+                }
+                #[cfg(feature = "rustls-tls")]
+                {
+                    builder = builder.use_rustls_tls();
+                }
+                builder.build()?
+            }
+        };
+
+        Ok(VyperClient {
+            base_url,
+            api_key,
+            http_client,
+            retry_policy: self.retry_policy.unwrap_or_default(),
+            rate_limiter: self.rate_limiter,
+            circuit_breaker: self.circuit_breaker,
+            cassette: self.cassette,
+            options: RequestOptions::default(),
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::circuit_breaker::CircuitState;
+    use futures_util::StreamExt;
     use mockito::{mock, server_url};
     use serde_json::json;
 
     fn setup_client() -> VyperClient {
         let mock_api_key = "test_api_key";
-        let mut client = VyperClient::new(mock_api_key);
-        client.base_url = server_url();
-        client
+        VyperClient::builder()
+            .api_key(mock_api_key)
+            .base_url(server_url())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_builder_requires_api_key() {
+        let result = VyperClient::builder().build();
+        assert!(matches!(result, Err(VyperError::ConfigurationError(_))));
+    }
+
+    #[test]
+    fn test_builder_rejects_invalid_base_url() {
+        let result = VyperClient::builder().api_key("key").base_url("not a url").build();
+        assert!(matches!(result, Err(VyperError::ConfigurationError(_))));
+    }
+
+    #[test]
+    fn test_builder_rejects_options_with_custom_http_client() {
+        let result = VyperClient::builder()
+            .api_key("key")
+            .http_client(HttpClient::new())
+            .timeout(Duration::from_secs(1))
+            .build();
+        assert!(matches!(result, Err(VyperError::ConfigurationError(_))));
+    }
+
+    #[test]
+    fn test_builder_trims_base_url() {
+        let client = VyperClient::builder()
+            .api_key("key")
+            .base_url("http://localhost:8080/")
+            .build()
+            .unwrap();
+        assert_eq!(client.base_url(), "http://localhost:8080");
+    }
+
+    #[tokio::test]
+    async fn test_builder_default_headers_and_user_agent() {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Trace", "abc".parse().unwrap());
+        let client = VyperClient::builder()
+            .api_key("test_api_key")
+            .base_url(server_url())
+            .user_agent("vyper-test/1.0")
+            .default_headers(headers)
+            .build()
+            .unwrap();
+
+        let _m = mock("GET", "/api/v1/chain/ids")
+            .match_header("X-API-Key", "test_api_key")
+            .match_header("X-Trace", "abc")
+            .match_header("user-agent", "vyper-test/1.0")
+            .with_status(200)
+            .with_body(r#"{"status":"success","message":"ok","data":{"ethereum":1}}"#)
+            .create();
+
+        let chain_ids = client.get_chain_ids().await.unwrap();
+        assert_eq!(chain_ids.get("ethereum"), Some(&ChainId::Ethereum));
+    }
+
+    fn setup_client_with_retries(max_attempts: u32) -> VyperClient {
+        VyperClient::builder()
+            .api_key("test_api_key")
+            .base_url(server_url())
+            .retry_policy(RetryPolicy {
+                max_attempts,
+                base_delay: Duration::from_millis(1),
+                jitter: 0.0,
+                ..Default::default()
+            })
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_retries_server_errors() {
+        let client = setup_client_with_retries(3);
+        let path = mockito::Matcher::Regex("^/api/v1/token/symbol".to_string());
+        let query = mockito::Matcher::UrlEncoded("tokenMint".into(), "retry-server-error".into());
+
+        let failure = mock("GET", path.clone())
+            .match_query(query.clone())
+            .with_status(503)
+            .with_body(r#"{"status":"error","message":"unavailable","data":null}"#)
+            .expect(3)
+            .create();
+
+        let result = client.get_token_symbol(ChainId::Ethereum, "retry-server-error").await;
+        assert!(matches!(result, Err(VyperError::ServerError { status: 503, .. })));
+        failure.assert();
+    }
+
+    #[tokio::test]
+    async fn test_retries_rate_limit_after_retry_after() {
+        let client = setup_client_with_retries(2);
+        let path = mockito::Matcher::Regex("^/api/v1/token/symbol".to_string());
+        let query = mockito::Matcher::UrlEncoded("tokenMint".into(), "retry-rate-limit".into());
+
+        let limited = mock("GET", path)
+            .match_query(query)
+            .with_status(429)
+            .with_header("Retry-After", "0")
+            .with_body(r#"{"status":"error","message":"slow down","data":null}"#)
+            .expect(2)
+            .create();
+
+        let result = client.get_token_symbol(ChainId::Ethereum, "retry-rate-limit").await;
+        match result {
+            Err(VyperError::RateLimitError { retry_after, .. }) => assert_eq!(retry_after, Some(0.0)),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        limited.assert();
+    }
+
+    #[tokio::test]
+    async fn test_does_not_retry_client_errors() {
+        let client = setup_client_with_retries(3);
+        let path = mockito::Matcher::Regex("^/api/v1/token/symbol".to_string());
+        let query = mockito::Matcher::UrlEncoded("tokenMint".into(), "retry-bad-request".into());
+
+        let bad_request = mock("GET", path)
+            .match_query(query)
+            .with_status(400)
+            .with_body(r#"{"status":"error","message":"bad request","data":null}"#)
+            .expect(1)
+            .create();
+
+        let result = client.get_token_symbol(ChainId::Ethereum, "retry-bad-request").await;
+        assert!(matches!(result, Err(VyperError::ApiError { status: 400, .. })));
+        bad_request.assert();
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_paces_cloned_clients() {
+        let client = VyperClient::builder()
+            .api_key("test_api_key")
+            .base_url(server_url())
+            .rate_limiter(RateLimiter::per_second(5).with_burst(1))
+            .build()
+            .unwrap();
+        let other = client.clone();
+
+        let _m = mock("GET", "/api/v1/token/symbol")
+            .match_query(mockito::Matcher::UrlEncoded("tokenMint".into(), "rate-limited".into()))
+            .with_status(200)
+            .with_body(r#"{"status":"success","message":"ok","data":{"symbol":"RL"}}"#)
+            .expect(3)
+            .create();
+
+        let start = std::time::Instant::now();
+        let (a, b) = tokio::join!(
+            client.get_token_symbol(ChainId::Ethereum, "rate-limited"),
+            other.get_token_symbol(ChainId::Ethereum, "rate-limited"),
+        );
+        client.get_token_symbol(ChainId::Ethereum, "rate-limited").await.unwrap();
+        assert!(a.is_ok() && b.is_ok());
+        assert!(start.elapsed() >= Duration::from_millis(400));
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_fails_fast_once_open() {
+        let breaker = CircuitBreaker::new(0.5, Duration::from_secs(60))
+            .with_window_size(2)
+            .with_minimum_requests(2);
+        let client = VyperClient::builder()
+            .api_key("test_api_key")
+            .base_url(server_url())
+            .retry_policy(RetryPolicy::none())
+            .circuit_breaker(breaker.clone())
+            .build()
+            .unwrap();
+
+        let failing = mock("GET", "/api/v1/token/symbol")
+            .match_query(mockito::Matcher::UrlEncoded("tokenMint".into(), "circuit-open".into()))
+            .with_status(503)
+            .with_body("unavailable")
+            .expect(2)
+            .create();
+
+        for _ in 0..2 {
+            let result = client.get_token_symbol(ChainId::Ethereum, "circuit-open").await;
+            assert!(matches!(result, Err(VyperError::ServerError { status: 503, .. })));
+        }
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        let result = client.clone().get_token_symbol(ChainId::Ethereum, "circuit-open").await;
+        assert!(matches!(result, Err(VyperError::CircuitOpen { retry_after: Some(_) })));
+        failing.assert();
+    }
+
+    /// Accepts connections but never answers them.
+    async fn unresponsive_server() -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut connections = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                connections.push(socket);
+            }
+        });
+        url
+    }
+
+    fn unresponsive_client(url: &str) -> VyperClient {
+        VyperClient::builder()
+            .api_key("test_api_key")
+            .base_url(url)
+            .retry_policy(RetryPolicy::none())
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_request_options_timeout() {
+        let client = unresponsive_client(&unresponsive_server().await);
+        let options = RequestOptions {
+            timeout: Some(Duration::from_millis(50)),
+            ..Default::default()
+        };
+
+        let result = client.with_options(options).get_chain_ids().await;
+        match result {
+            Err(VyperError::HttpError(e)) => assert!(e.is_timeout()),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_request_options_deadline() {
+        let client = unresponsive_client(&unresponsive_server().await);
+        let options = RequestOptions::default().deadline_in(Duration::from_millis(50));
+
+        let start = Instant::now();
+        let result = client.with_options(options).get_chain_ids().await;
+        assert!(matches!(result, Err(VyperError::DeadlineExceeded)));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_request_options_cancellation() {
+        let client = unresponsive_client(&unresponsive_server().await);
+        let token = tokio_util::sync::CancellationToken::new();
+        let options = RequestOptions {
+            cancellation_token: Some(token.clone()),
+            ..Default::default()
+        };
+
+        let canceller = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            token.cancel();
+        });
+        let result = client.with_options(options).get_chain_ids().await;
+        assert!(matches!(result, Err(VyperError::Cancelled)));
+        canceller.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_request_options_headers_and_retry_override() {
+        let client = setup_client_with_retries(3);
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Trace-Id", "trace-1".parse().unwrap());
+        let options = RequestOptions {
+            headers,
+            retry_policy: Some(RetryPolicy::none()),
+            ..Default::default()
+        };
+
+        let failure = mock("GET", "/api/v1/token/symbol")
+            .match_query(mockito::Matcher::UrlEncoded("tokenMint".into(), "options-override".into()))
+            .match_header("X-Trace-Id", "trace-1")
+            .with_status(503)
+            .with_body("unavailable")
+            .expect(1)
+            .create();
+
+        let scoped = client.with_options(options);
+        let result = scoped.get_token_symbol(ChainId::Ethereum, "options-override").await;
+        assert!(matches!(result, Err(VyperError::ServerError { status: 503, .. })));
+        assert!(client.options().retry_policy.is_none());
+        failure.assert();
+    }
+
+    fn token_pair_json(market_id: &str) -> serde_json::Value {
+        json!({
+            "buyTxnCount": 10,
+            "chainId": 900,
+            "contractCreator": "creator",
+            "createdTimestamp": 1609459200,
+            "initialAssetLiquidity": 1.0,
+            "initialUsdLiquidity": 1.0,
+            "lpBurned": true,
+            "lpCreator": "lp",
+            "marketId": market_id,
+            "name": "Streamed",
+            "pooledAsset": 1.0,
+            "pooledToken": 1.0,
+            "priceChangePercent": 0.0,
+            "sellTxnCount": 5,
+            "symbol": "STR",
+            "tokenLiquidityAsset": 1.0,
+            "tokenLiquidityUsd": 1.0,
+            "tokenMarketCapAsset": 1.0,
+            "tokenMarketCapUsd": 1.0,
+            "tokenMint": "mint",
+            "tokenPriceAsset": 1.0,
+            "tokenPriceUsd": 1.0,
+            "tokenType": "token",
+            "top10HoldingPercent": 1.0,
+            "totalSupply": 1.0,
+            "transactionCount": 15,
+            "volumeAsset": 1.0,
+            "volumeUsd": 1.0
+        })
+    }
+
+    fn mock_pairs_page(page: i32, has_next: bool, market_ids: &[&str]) -> mockito::Mock {
+        let pairs: Vec<_> = market_ids.iter().map(|id| token_pair_json(id)).collect();
+        mock("GET", "/api/v1/token/pairs")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("tokenTypes".into(), "stream-test".into()),
+                mockito::Matcher::UrlEncoded("page".into(), page.to_string()),
+            ]))
+            .with_status(200)
+            .with_body(json!({"status": "success", "message": "ok", "data": {"hasNext": has_next, "pairs": pairs}}).to_string())
+            .create()
+    }
+
+    #[tokio::test]
+    async fn test_token_pairs_stream() {
+        let client = setup_client();
+        let _p1 = mock_pairs_page(1, true, &["m1", "m2"]);
+        let _p2 = mock_pairs_page(2, true, &["m3"]);
+        let _p3 = mock_pairs_page(3, false, &["m4"]);
+
+        let params = TokenPairsParams {
+            token_types: Some(vec!["stream-test".to_string()]),
+            ..Default::default()
+        };
+
+        let all: Vec<String> = client
+            .token_pairs_stream(params.clone())
+            .map(|pair| pair.unwrap().market_id)
+            .collect()
+            .await;
+        assert_eq!(all, vec!["m1", "m2", "m3", "m4"]);
+
+        let options = PaginationOptions {
+            max_pages: Some(2),
+            prefetch: true,
+        };
+        let capped: Vec<String> = client
+            .token_pairs_stream_with(params, options)
+            .map(|pair| pair.unwrap().market_id)
+            .collect()
+            .await;
+        assert_eq!(capped, vec!["m1", "m2", "m3"]);
     }
 
     #[tokio::test]
@@ -204,8 +1061,8 @@ mod tests {
         let result = client.get_chain_ids().await;
         assert!(result.is_ok());
         let chain_ids = result.unwrap();
-        assert_eq!(chain_ids.get("ethereum"), Some(&1));
-        assert_eq!(chain_ids.get("solana"), Some(&900));
+        assert_eq!(chain_ids.get("ethereum"), Some(&ChainId::Ethereum));
+        assert_eq!(chain_ids.get("solana"), Some(&ChainId::Solana));
     }
 
     #[tokio::test]
@@ -231,12 +1088,12 @@ mod tests {
             .with_body(mock_response.to_string())
             .create();
 
-        let result = client.get_token_ath(1, "test-market").await;
+        let result = client.get_token_ath(ChainId::Ethereum, "test-market").await;
         assert!(result.is_ok());
         let token_ath = result.unwrap();
-        assert_eq!(token_ath.market_cap_usd, 1000000.0);
-        assert_eq!(token_ath.timestamp, 1632825600);
-        assert_eq!(token_ath.token_liquidity_usd, 500000.0);
+        assert_eq!(token_ath.market_cap_usd, Number::from(1000000));
+        assert_eq!(timestamp_millis(token_ath.timestamp), 1632825600000);
+        assert_eq!(token_ath.token_liquidity_usd, Number::from(500000));
     }
 
     #[tokio::test]
@@ -301,14 +1158,14 @@ mod tests {
             .with_body(mock_response)
             .create();
     
-        let result = client.get_token_market("test-market", 1, "1d").await;
+        let result = client.get_token_market("test-market", ChainId::Ethereum, Interval::OneDay).await;
         if let Err(e) = &result {
             println!("Error: {:?}", e);
         }
         assert!(result.is_ok());
         let token_pair = result.unwrap();
         assert_eq!(token_pair.market_id, "test-market");
-        assert_eq!(token_pair.chain_id, 1);
+        assert_eq!(token_pair.chain_id, ChainId::Ethereum);
         assert_eq!(token_pair.name, "Bitcoin");
         assert_eq!(token_pair.symbol, "BTC");
     }
@@ -342,12 +1199,148 @@ mod tests {
             .with_body(mock_response.to_string())
             .create();
 
-        let result = client.get_token_holders("test-market", 1).await;
+        let result = client.get_token_holders("test-market", ChainId::Ethereum).await;
         assert!(result.is_ok());
-        let (holders, total_holders) = result.unwrap();
-        assert_eq!(holders.len(), 1);
-        assert_eq!(holders[0].wallet_address, "0x123...");
-        assert_eq!(total_holders, 1000);
+        let holders = result.unwrap();
+        assert_eq!(holders.holders.len(), 1);
+        assert_eq!(holders.holders[0].wallet_address, "0x123...");
+        assert_eq!(holders.total_holders, 1000);
+    }
+
+    #[tokio::test]
+    async fn test_get_token_holders_missing_total_is_an_error() {
+        let client = setup_client();
+        let _m = mock("GET", "/api/v1/token/holders")
+            .match_query(mockito::Matcher::UrlEncoded("marketID".into(), "holders-no-total".into()))
+            .with_status(200)
+            .with_body(r#"{"status":"success","message":"ok","data":{"holders":[],"total_holders":null}}"#)
+            .create();
+
+        let result = client.get_token_holders("holders-no-total", ChainId::Ethereum).await;
+        assert!(matches!(result, Err(VyperError::DeserializeError(_))));
+
+        let params = TokenHoldersParams {
+            limit: Some(0),
+            ..Default::default()
+        };
+        let result = client.get_token_holders_page("holders-no-total", ChainId::Ethereum, params).await;
+        assert!(matches!(result, Err(VyperError::InvalidParams(ParamsError::InvalidLimit(0)))));
+    }
+
+    fn mock_holders_page(market_id: &str, page: i32, wallets: &[&str], total: i32) -> mockito::Mock {
+        mock_holders(market_id, page, Some(2), wallets, total)
+    }
+
+    fn mock_holders(market_id: &str, page: i32, limit: Option<i32>, wallets: &[&str], total: i32) -> mockito::Mock {
+        let mut query = vec![
+            mockito::Matcher::UrlEncoded("marketID".into(), market_id.into()),
+            mockito::Matcher::UrlEncoded("page".into(), page.to_string()),
+        ];
+        if let Some(limit) = limit {
+            query.push(mockito::Matcher::UrlEncoded("limit".into(), limit.to_string()));
+        }
+        let holders: Vec<_> = wallets
+            .iter()
+            .map(|wallet| json!({"walletAddress": wallet, "tokenHoldings": 1.0, "usdHoldings": 1.0, "percentOwned": 0.1}))
+            .collect();
+        mock("GET", "/api/v1/token/holders")
+            .match_query(mockito::Matcher::AllOf(query))
+            .with_status(200)
+            .with_body(json!({"status": "success", "message": "ok", "data": {"holders": holders, "total_holders": total}}).to_string())
+            .expect(1)
+            .create()
+    }
+
+    #[tokio::test]
+    async fn test_token_holders_stream_stops_at_total() {
+        let client = setup_client();
+        let pages = vec![
+            mock_holders_page("holders-stream", 1, &["a", "b"], 4),
+            mock_holders_page("holders-stream", 2, &["c", "d"], 4),
+        ];
+        let params = TokenHoldersParams {
+            limit: Some(2),
+            ..Default::default()
+        };
+
+        let wallets: Vec<String> = client
+            .token_holders_stream_with("holders-stream", ChainId::Solana, params, PaginationOptions::default())
+            .map(|holder| holder.unwrap().wallet_address)
+            .collect()
+            .await;
+        assert_eq!(wallets, vec!["a", "b", "c", "d"]);
+        for page in pages {
+            page.assert();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_token_holders_stream_stops_at_short_page() {
+        let client = setup_client();
+        let pages = vec![
+            mock_holders_page("holders-short", 1, &["a", "b"], 100),
+            mock_holders_page("holders-short", 2, &["c"], 100),
+        ];
+        let params = TokenHoldersParams {
+            limit: Some(2),
+            ..Default::default()
+        };
+
+        let count = client
+            .token_holders_stream_with("holders-short", ChainId::Solana, params, PaginationOptions::default())
+            .count()
+            .await;
+        assert_eq!(count, 3);
+        for page in pages {
+            page.assert();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_token_holders_stream_from_later_page() {
+        let client = setup_client();
+        let pages = vec![
+            mock_holders_page("holders-later", 2, &["c", "d"], 5),
+            mock_holders_page("holders-later", 3, &["e"], 5),
+        ];
+        let params = TokenHoldersParams {
+            page: Some(2),
+            limit: Some(2),
+        };
+
+        let wallets: Vec<String> = client
+            .token_holders_stream_with("holders-later", ChainId::Solana, params, PaginationOptions::default())
+            .map(|holder| holder.unwrap().wallet_address)
+            .collect()
+            .await;
+        assert_eq!(wallets, vec!["c", "d", "e"]);
+        for page in pages {
+            page.assert();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_token_holders_stream_without_limit_stops_at_empty_page() {
+        let client = setup_client();
+        let pages = vec![
+            mock_holders("holders-unlimited", 2, None, &["c", "d", "e"], 6),
+            mock_holders("holders-unlimited", 3, None, &["f"], 6),
+            mock_holders("holders-unlimited", 4, None, &[], 6),
+        ];
+        let params = TokenHoldersParams {
+            page: Some(2),
+            ..Default::default()
+        };
+
+        let wallets: Vec<String> = client
+            .token_holders_stream_with("holders-unlimited", ChainId::Solana, params, PaginationOptions::default())
+            .map(|holder| holder.unwrap().wallet_address)
+            .collect()
+            .await;
+        assert_eq!(wallets, vec!["c", "d", "e", "f"]);
+        for page in pages {
+            page.assert();
+        }
     }
 
     #[tokio::test]
@@ -376,7 +1369,7 @@ mod tests {
             .with_body(mock_response.to_string())
             .create();
 
-        let result = client.get_token_markets("0x123...", 1).await;
+        let result = client.get_token_markets("0x123...", ChainId::Ethereum).await;
         assert!(result.is_ok());
         let token_markets = result.unwrap();
         assert_eq!(token_markets.len(), 1);
@@ -409,7 +1402,7 @@ mod tests {
             .with_body(mock_response.to_string())
             .create();
 
-        let result = client.get_wallet_holdings("0xabc...", 1).await;
+        let result = client.get_wallet_holdings("0xabc...", ChainId::Ethereum).await;
         assert!(result.is_ok());
         let holdings = result.unwrap();
         assert_eq!(holdings.len(), 1);
@@ -445,10 +1438,10 @@ mod tests {
             .with_body(mock_response.to_string())
             .create();
 
-        let result = client.get_wallet_aggregated_pnl("0xabc...", 1).await;
+        let result = client.get_wallet_aggregated_pnl("0xabc...", ChainId::Ethereum).await;
         assert!(result.is_ok());
         let pnl = result.unwrap();
-        assert_eq!(pnl.invested_amount, 100000.0);
+        assert_eq!(pnl.invested_amount, Number::from(100000));
         assert_eq!(pnl.pnl_percent, 10.0);
     }
 
@@ -482,11 +1475,11 @@ mod tests {
             .with_body(mock_response.to_string())
             .create();
 
-        let result = client.get_wallet_pnl("0xabc...", "test-market", 1).await;
+        let result = client.get_wallet_pnl("0xabc...", "test-market", ChainId::Ethereum).await;
         assert!(result.is_ok());
         let pnl = result.unwrap();
-        assert_eq!(pnl.holder_since, 1609459200);
-        assert_eq!(pnl.invested_amount, 50000.0);
+        assert_eq!(timestamp_millis(pnl.holder_since), 1609459200000);
+        assert_eq!(pnl.invested_amount, Number::from(50000));
     }
 
     #[tokio::test]
@@ -515,7 +1508,7 @@ mod tests {
             .with_body(mock_response.to_string())
             .create();
 
-        let result = client.get_token_metadata(1, "0x123...").await;
+        let result = client.get_token_metadata(ChainId::Ethereum, "0x123...").await;
         assert!(result.is_ok());
         let metadata = result.unwrap();
         assert_eq!(metadata.name, "Example Token");
@@ -543,7 +1536,7 @@ mod tests {
             .with_body(mock_response.to_string())
             .create();
 
-        let result = client.get_token_symbol(1, "0x123...").await;
+        let result = client.get_token_symbol(ChainId::Ethereum, "0x123...").await;
         assert!(result.is_ok());
         let symbol = result.unwrap();
         assert_eq!(symbol.symbol, "EXT");
@@ -580,7 +1573,7 @@ mod tests {
             .with_body(mock_response.to_string())
             .create();
 
-        let result = client.get_top_traders("test-market", 1).await;
+        let result = client.get_top_traders("test-market", ChainId::Ethereum).await;
         assert!(result.is_ok());
         let top_traders = result.unwrap();
         assert_eq!(top_traders.len(), 1);
@@ -624,7 +1617,7 @@ mod tests {
             .with_body(mock_response.to_string())
             .create();
 
-        let result = client.search_tokens("EXT", Some(1)).await;
+        let result = client.search_tokens("EXT", Some(ChainId::Ethereum)).await;
         assert!(result.is_ok());
         let search_results = result.unwrap();
         assert_eq!(search_results.len(), 1);
@@ -696,7 +1689,7 @@ mod tests {
             .create();
 
         let params = TokenPairsParams {
-            chain_ids: Some(vec![1]),
+            chain_ids: Some(vec![ChainId::Ethereum]),
             page: Some(1),
             ..Default::default()
         };
@@ -704,9 +1697,123 @@ mod tests {
         let result = client.get_token_pairs(params).await;
         assert!(result.is_ok());
         let token_pairs = result.unwrap();
-        assert_eq!(token_pairs.has_next, false);
+        assert!(!token_pairs.has_next);
         assert_eq!(token_pairs.pairs.len(), 1);
         assert_eq!(token_pairs.pairs[0].market_id, "test-market");
         assert_eq!(token_pairs.pairs[0].symbol, "EXT");
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_get_token_pairs_rejects_invalid_params() {
+        let client = setup_client();
+        let params = TokenPairsParams {
+            liquidity_min: Some(10.0),
+            liquidity_max: Some(1.0),
+            ..Default::default()
+        };
+
+        let result = client.get_token_pairs(params).await;
+        assert!(matches!(
+            result,
+            Err(VyperError::InvalidParams(ParamsError::InvalidRange { field: "liquidity", .. }))
+        ));
+    }
+
+    #[test]
+    fn test_error_from_json_body() {
+        let body = r#"{"status":"error","message":"market not found","data":null}"#;
+        let err = error_from_response(reqwest::StatusCode::NOT_FOUND, &HeaderMap::new(), body);
+        match err {
+            VyperError::ApiError { message, status, body } => {
+                assert_eq!(message, "market not found");
+                assert_eq!(status, 404);
+                assert!(body.unwrap().contains("market not found"));
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+
+        let err = error_from_response(reqwest::StatusCode::UNAUTHORIZED, &HeaderMap::new(), r#"{"message":"bad key"}"#);
+        assert!(matches!(err, VyperError::AuthenticationError(ref m) if m == "bad key"));
+    }
+
+    #[test]
+    fn test_error_from_html_body_is_truncated() {
+        let body = format!("<html><body>{}</body></html>", "x".repeat(2000));
+        let err = error_from_response(reqwest::StatusCode::BAD_GATEWAY, &HeaderMap::new(), &body);
+        match err {
+            VyperError::ServerError { message, status, body } => {
+                assert_eq!(status, 502);
+                let body = body.unwrap();
+                assert!(body.starts_with("<html>"));
+                assert_eq!(body.chars().count(), MAX_ERROR_BODY_CHARS + 3);
+                assert_eq!(message, body);
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_error_from_empty_body() {
+        let err = error_from_response(reqwest::StatusCode::GATEWAY_TIMEOUT, &HeaderMap::new(), "  ");
+        match err {
+            VyperError::ServerError { message, status, body } => {
+                assert_eq!(message, "504 Gateway Timeout");
+                assert_eq!(status, 504);
+                assert!(body.is_none());
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+
+        let mut headers = HeaderMap::new();
+        headers.insert("Retry-After", "3".parse().unwrap());
+        let err = error_from_response(reqwest::StatusCode::TOO_MANY_REQUESTS, &headers, "");
+        assert!(matches!(err, VyperError::RateLimitError { retry_after: Some(r), .. } if r == 3.0));
+    }
+
+    #[tokio::test]
+    async fn test_non_json_error_response() {
+        let client = VyperClient::builder()
+            .api_key("test_api_key")
+            .base_url(server_url())
+            .retry_policy(RetryPolicy::none())
+            .build()
+            .unwrap();
+
+        let _m = mock("GET", "/api/v1/token/symbol")
+            .match_query(mockito::Matcher::UrlEncoded("tokenMint".into(), "html-502".into()))
+            .with_status(502)
+            .with_header("content-type", "text/html")
+            .with_body("<html><h1>502 Bad Gateway</h1></html>")
+            .create();
+
+        let err = client.get_token_symbol(ChainId::Ethereum, "html-502").await.unwrap_err();
+        assert_eq!(err.status_code(), Some(502));
+        assert!(matches!(err, VyperError::ServerError { body: Some(ref b), .. } if b.contains("Bad Gateway")));
+    }
+
+    #[tokio::test]
+    async fn test_response_metadata() {
+        let client = setup_client();
+        let _m = mock("GET", "/api/v1/token/symbol")
+            .match_query(mockito::Matcher::UrlEncoded("tokenMint".into(), "with-meta".into()))
+            .with_status(200)
+            .with_header("X-RateLimit-Limit", "600")
+            .with_header("X-RateLimit-Remaining", "599")
+            .with_header("X-RateLimit-Reset", "60")
+            .with_header("X-Request-Id", "req-abc")
+            .with_body(r#"{"status":"success","message":"Token symbol retrieved successfully","data":{"symbol":"META"}}"#)
+            .create();
+
+        let response = client.get_token_symbol_with_meta(ChainId::Ethereum, "with-meta").await.unwrap();
+        assert_eq!(response.status, reqwest::StatusCode::OK);
+        assert_eq!(response.api_status, "success");
+        assert_eq!(response.message, "Token symbol retrieved successfully");
+        assert_eq!(response.rate_limit.limit, Some(600));
+        assert_eq!(response.rate_limit.remaining, Some(599));
+        assert_eq!(response.rate_limit.reset, Some(60));
+        assert_eq!(response.request_id.as_deref(), Some("req-abc"));
+        assert_eq!(response.attempts, 1);
+        assert_eq!(response.headers.get("x-request-id").unwrap(), "req-abc");
+        assert_eq!(response.into_data().symbol, "META");
+    }
+}
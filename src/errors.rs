@@ -17,11 +17,47 @@ pub enum VyperError {
         retry_after: Option<f64>,
     },
 
-    #[error("Server error: {0}")]
-    ServerError(String),
+    /// A 5xx response. `body` holds the (truncated) raw response body, if any.
+    #[error("Server error: {message} (Status: {status})")]
+    ServerError {
+        message: String,
+        status: u16,
+        body: Option<String>,
+    },
+
+    /// Any other non-success response. `body` holds the (truncated) raw
+    /// response body, if any.
+    #[error("API error: {message} (Status: {status})")]
+    ApiError {
+        message: String,
+        status: u16,
+        body: Option<String>,
+    },
+
+    #[error("Invalid parameters: {0}")]
+    InvalidParams(#[from] ParamsError),
+
+    #[error("Cassette error: {0}")]
+    CassetteError(String),
+
+    /// The client's circuit breaker is open and the request was not sent.
+    /// `retry_after` is the remaining cooldown, or `None` while a half-open
+    /// probe request is in flight.
+    #[error("Circuit breaker is open")]
+    CircuitOpen { retry_after: Option<std::time::Duration> },
+
+    /// The request was aborted through its
+    /// [`RequestOptions::cancellation_token`](crate::options::RequestOptions::cancellation_token).
+    #[error("Request cancelled")]
+    Cancelled,
+
+    /// The [`RequestOptions::deadline`](crate::options::RequestOptions::deadline)
+    /// passed before the request completed.
+    #[error("Request deadline exceeded")]
+    DeadlineExceeded,
 
-    #[error("API error: {0} (Status: {1})")]
-    ApiError(String, u16),
+    #[error("Configuration error: {0}")]
+    ConfigurationError(String),
 
     #[error("Websocket error: {message}")]
     WebsocketError {
@@ -31,7 +67,42 @@ pub enum VyperError {
     },
 }
 
+/// Request parameters rejected on the client before any HTTP call is made.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ParamsError {
+    #[error("{field}: minimum {min} is greater than maximum {max}")]
+    InvalidRange {
+        field: &'static str,
+        min: f64,
+        max: f64,
+    },
+
+    #[error("{field} must be a non-negative number, got {value}")]
+    Negative { field: &'static str, value: f64 },
+
+    #[error("{field} must be a number, got NaN")]
+    NotANumber { field: &'static str },
+
+    #[error("page must be at least 1, got {0}")]
+    InvalidPage(i32),
+
+    #[error("limit must be at least 1, got {0}")]
+    InvalidLimit(i32),
+}
+
 impl VyperError {
+    /// The HTTP status associated with this error, when there is one.
+    pub fn status_code(&self) -> Option<u16> {
+        match self {
+            VyperError::HttpError(e) => e.status().map(|s| s.as_u16()),
+            VyperError::AuthenticationError(_) => Some(401),
+            VyperError::RateLimitError { .. } => Some(429),
+            VyperError::ServerError { status, .. } | VyperError::ApiError { status, .. } => Some(*status),
+            VyperError::WebsocketError { status_code, .. } => *status_code,
+            _ => None,
+        }
+    }
+
     pub fn websocket_error<S: Into<String>>(message: S, status_code: Option<u16>, connection_info: Option<String>) -> Self {
         VyperError::WebsocketError {
             message: message.into(),
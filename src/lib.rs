@@ -1,4 +1,23 @@
+#[cfg(all(feature = "native-tls", feature = "rustls-tls"))]
+compile_error!("features `native-tls` and `rustls-tls` are mutually exclusive; disable default features to use `rustls-tls`");
+
+#[cfg(not(any(feature = "native-tls", feature = "rustls-tls")))]
+compile_error!("one of the `native-tls` or `rustls-tls` features must be enabled");
+
 pub mod websocket ;
 pub mod types;
 pub mod errors;
-pub mod client;
\ No newline at end of file
+pub mod client;
+pub mod retry;
+pub mod rate_limit;
+pub mod circuit_breaker;
+pub mod pagination;
+pub mod response;
+pub mod options;
+pub mod api;
+pub mod cassette;
+pub mod portfolio;
+pub mod search;
+mod chains;
+#[cfg(feature = "blocking")]
+pub mod blocking;
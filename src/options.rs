@@ -0,0 +1,50 @@
+use std::time::{Duration, Instant};
+use reqwest::header::HeaderMap;
+use tokio_util::sync::CancellationToken;
+use crate::retry::RetryPolicy;
+
+/// Per-call overrides, applied with
+/// [`VyperClient::with_options`](crate::client::VyperClient::with_options).
+///
+/// ```no_run
+/// # async fn run(client: vyper_client_rs::client::VyperClient) -> Result<(), vyper_client_rs::errors::VyperError> {
+/// use std::time::Duration;
+/// use vyper_client_rs::options::RequestOptions;
+/// use vyper_client_rs::types::ChainId;
+///
+/// let holders = client
+///     .with_options(RequestOptions {
+///         timeout: Some(Duration::from_secs(60)),
+///         ..Default::default()
+///     })
+///     .get_token_holders("market_id", ChainId::Solana)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RequestOptions {
+    /// Timeout for each attempt, replacing the client-wide timeout.
+    pub timeout: Option<Duration>,
+    /// Point in time by which the call, including retries and rate limiter
+    /// waits, must finish. Past it the call fails with
+    /// [`VyperError::DeadlineExceeded`](crate::errors::VyperError::DeadlineExceeded);
+    /// a retry whose backoff would end after it is skipped and the last error
+    /// returned instead.
+    pub deadline: Option<Instant>,
+    /// Headers added to every request, overriding client defaults of the same name.
+    pub headers: HeaderMap,
+    /// Replaces the client's retry policy.
+    pub retry_policy: Option<RetryPolicy>,
+    /// Cancelling the token aborts the call, including any pending retries,
+    /// with [`VyperError::Cancelled`](crate::errors::VyperError::Cancelled).
+    pub cancellation_token: Option<CancellationToken>,
+}
+
+impl RequestOptions {
+    /// Sets `deadline` to `timeout` from now.
+    pub fn deadline_in(mut self, timeout: Duration) -> Self {
+        self.deadline = Some(Instant::now() + timeout);
+        self
+    }
+}
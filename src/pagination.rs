@@ -0,0 +1,181 @@
+use std::future::Future;
+use futures_util::stream::{self, BoxStream, StreamExt};
+use tokio::task::JoinHandle;
+use crate::errors::VyperError;
+
+/// Controls how paginated streams such as
+/// [`VyperClient::token_pairs_stream_with`](crate::client::VyperClient::token_pairs_stream_with)
+/// walk through pages.
+#[derive(Debug, Clone, Default)]
+pub struct PaginationOptions {
+    /// Stop after this many pages even if the server reports more.
+    pub max_pages: Option<usize>,
+    /// Request the next page in the background while the current one is consumed.
+    pub prefetch: bool,
+}
+
+pub(crate) struct Page<T> {
+    pub items: Vec<T>,
+    pub has_next: bool,
+}
+
+struct AbortOnDrop<T>(JoinHandle<T>);
+
+impl<T> Drop for AbortOnDrop<T> {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+struct State<T, F> {
+    fetch: F,
+    page: i32,
+    pages_fetched: usize,
+    done: bool,
+    prefetched: Option<AbortOnDrop<Result<Page<T>, VyperError>>>,
+}
+
+/// Turns a page fetcher into a stream of items, starting at `first_page` and
+/// stopping when a page reports no successor, `max_pages` is reached or a
+/// request fails (the error is yielded as the final item).
+pub(crate) fn paginate<T, F, Fut>(
+    fetch: F,
+    first_page: i32,
+    options: PaginationOptions,
+) -> BoxStream<'static, Result<T, VyperError>>
+where
+    T: Send + 'static,
+    F: Fn(i32) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<Page<T>, VyperError>> + Send + 'static,
+{
+    let state = State {
+        fetch,
+        page: first_page,
+        pages_fetched: 0,
+        done: options.max_pages == Some(0),
+        prefetched: None,
+    };
+
+    stream::unfold(state, move |mut state| {
+        let options = options.clone();
+        async move {
+            if state.done {
+                return None;
+            }
+
+            let result = match state.prefetched.take() {
+                Some(mut handle) => match (&mut handle.0).await {
+                    Ok(result) => result,
+                    Err(e) => std::panic::resume_unwind(e.into_panic()),
+                },
+                None => (state.fetch)(state.page).await,
+            };
+
+            match result {
+                Ok(page) => {
+                    state.pages_fetched += 1;
+                    state.page += 1;
+                    let under_cap = !matches!(options.max_pages, Some(max) if state.pages_fetched >= max);
+                    if page.has_next && under_cap {
+                        if options.prefetch {
+                            let next = (state.fetch)(state.page);
+                            state.prefetched = Some(AbortOnDrop(tokio::spawn(next)));
+                        }
+                    } else {
+                        state.done = true;
+                    }
+                    Some((Ok(page.items), state))
+                }
+                Err(e) => {
+                    state.done = true;
+                    Some((Err(e), state))
+                }
+            }
+        }
+    })
+    .flat_map(|result| {
+        let items: Vec<Result<T, VyperError>> = match result {
+            Ok(items) => items.into_iter().map(Ok).collect(),
+            Err(e) => vec![Err(e)],
+        };
+        stream::iter(items)
+    })
+    .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn numbered_pages(last_page: i32, calls: Arc<AtomicUsize>) -> impl Fn(i32) -> futures_util::future::Ready<Result<Page<i32>, VyperError>> {
+        move |page| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            futures_util::future::ready(Ok(Page {
+                items: vec![page * 10, page * 10 + 1],
+                has_next: page < last_page,
+            }))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_walks_until_has_next_is_false() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let items: Vec<i32> = paginate(numbered_pages(3, calls.clone()), 1, PaginationOptions::default())
+            .map(|r| r.unwrap())
+            .collect()
+            .await;
+        assert_eq!(items, vec![10, 11, 20, 21, 30, 31]);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_max_pages() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let options = PaginationOptions {
+            max_pages: Some(2),
+            ..Default::default()
+        };
+        let items: Vec<i32> = paginate(numbered_pages(10, calls.clone()), 4, options)
+            .map(|r| r.unwrap())
+            .collect()
+            .await;
+        assert_eq!(items, vec![40, 41, 50, 51]);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_prefetch_yields_same_items() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let options = PaginationOptions {
+            prefetch: true,
+            ..Default::default()
+        };
+        let items: Vec<i32> = paginate(numbered_pages(3, calls.clone()), 1, options)
+            .map(|r| r.unwrap())
+            .collect()
+            .await;
+        assert_eq!(items, vec![10, 11, 20, 21, 30, 31]);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_error_ends_stream() {
+        let fetch = |page: i32| async move {
+            if page == 1 {
+                Ok(Page { items: vec![1], has_next: true })
+            } else {
+                Err(VyperError::ServerError {
+                    message: "boom".to_string(),
+                    status: 500,
+                    body: None,
+                })
+            }
+        };
+        let results: Vec<Result<i32, VyperError>> = paginate(fetch, 1, PaginationOptions::default()).collect().await;
+        assert_eq!(results.len(), 2);
+        assert!(matches!(results[0], Ok(1)));
+        assert!(matches!(results[1], Err(VyperError::ServerError { .. })));
+    }
+}
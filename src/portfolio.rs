@@ -0,0 +1,181 @@
+use futures_util::future::try_join;
+use crate::chains::ChainResults;
+use crate::client::VyperClient;
+use crate::errors::VyperError;
+use crate::types::{ChainId, Number, WalletAggregatedPnL, WalletHolding};
+
+/// A wallet's holdings and PnL across several chains, returned by
+/// [`VyperClient::get_wallet_portfolio`].
+#[derive(Debug)]
+pub struct WalletPortfolio {
+    pub wallet_address: String,
+    /// One entry per chain that was fetched successfully, ordered by chain id.
+    pub chains: Vec<ChainPortfolio>,
+    /// Chains that could not be fetched, with the error for each.
+    pub failures: Vec<(ChainId, VyperError)>,
+    /// Sum of `usd_value` over every successful chain.
+    pub total_usd_value: Number,
+    /// Sum of `pnl.total_pnl_usd` over every successful chain.
+    pub total_pnl_usd: Number,
+    /// Sum of `pnl.unrealized_pnl_usd` over every successful chain.
+    pub total_unrealized_pnl_usd: Number,
+    /// Sum of `pnl.invested_amount` over every successful chain.
+    pub total_invested_amount: Number,
+}
+
+#[derive(Debug)]
+pub struct ChainPortfolio {
+    pub chain_id: ChainId,
+    pub holdings: Vec<WalletHolding>,
+    pub pnl: WalletAggregatedPnL,
+    /// Sum of the `usd_value` of `holdings`.
+    pub usd_value: Number,
+}
+
+impl WalletPortfolio {
+    /// `true` when every requested chain was fetched.
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    pub fn chain(&self, chain_id: ChainId) -> Option<&ChainPortfolio> {
+        self.chains.iter().find(|chain| chain.chain_id == chain_id)
+    }
+
+    fn from_results(wallet_address: &str, results: ChainResults<ChainPortfolio>) -> Self {
+        let mut portfolio = WalletPortfolio {
+            wallet_address: wallet_address.to_string(),
+            chains: Vec::new(),
+            failures: results.failures,
+            total_usd_value: Number::default(),
+            total_pnl_usd: Number::default(),
+            total_unrealized_pnl_usd: Number::default(),
+            total_invested_amount: Number::default(),
+        };
+        for (_, chain) in results.succeeded {
+            portfolio.total_usd_value += chain.usd_value;
+            portfolio.total_pnl_usd += chain.pnl.total_pnl_usd;
+            portfolio.total_unrealized_pnl_usd += chain.pnl.unrealized_pnl_usd;
+            portfolio.total_invested_amount += chain.pnl.invested_amount;
+            portfolio.chains.push(chain);
+        }
+        portfolio
+    }
+}
+
+impl VyperClient {
+    /// Fetches holdings and aggregated PnL for `wallet_address` on every chain
+    /// in `chains` concurrently and combines them. An empty `chains` means
+    /// every chain reported by [`get_chain_ids`](VyperClient::get_chain_ids).
+    ///
+    /// A chain that fails is recorded in [`WalletPortfolio::failures`] and left
+    /// out of the totals. An error is only returned when the chain list cannot
+    /// be fetched or when every chain failed.
+    pub async fn get_wallet_portfolio(&self, wallet_address: &str, chains: &[ChainId]) -> Result<WalletPortfolio, VyperError> {
+        let results = self
+            .fan_out_chains(chains, |chain_id| self.get_chain_portfolio(wallet_address, chain_id))
+            .await?;
+        Ok(WalletPortfolio::from_results(wallet_address, results))
+    }
+
+    async fn get_chain_portfolio(&self, wallet_address: &str, chain_id: ChainId) -> Result<ChainPortfolio, VyperError> {
+        let (holdings, pnl) = try_join(
+            self.get_wallet_holdings(wallet_address, chain_id),
+            self.get_wallet_aggregated_pnl(wallet_address, chain_id),
+        )
+        .await?;
+        Ok(ChainPortfolio {
+            chain_id,
+            usd_value: holdings.iter().map(|holding| holding.usd_value).sum(),
+            holdings,
+            pnl,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chains::setup_client;
+    use mockito::{mock, Matcher};
+    use serde_json::json;
+
+    fn wallet_query(wallet: &str, chain_id: ChainId) -> Matcher {
+        Matcher::AllOf(vec![
+            Matcher::UrlEncoded("walletAddress".into(), wallet.into()),
+            Matcher::UrlEncoded("chainID".into(), chain_id.id().to_string()),
+        ])
+    }
+
+    fn number(value: &str) -> Number {
+        value.parse().unwrap()
+    }
+
+    fn mock_chain(wallet: &str, chain_id: ChainId, usd_values: &[f64], pnl_usd: f64) -> Vec<mockito::Mock> {
+        let holdings: Vec<_> = usd_values
+            .iter()
+            .map(|usd| json!({"marketId": "m", "tokenHoldings": 1.0, "tokenSymbol": "T", "usdValue": usd}))
+            .collect();
+        let pnl = json!({
+            "investedAmount": 100.0,
+            "pnlPercent": 0.0,
+            "pnlUsd": pnl_usd,
+            "soldAmount": 0.0,
+            "tokensTraded": 1,
+            "totalPnlPercent": 0.0,
+            "totalPnlUsd": pnl_usd,
+            "unrealizedPnlPercent": 0.0,
+            "unrealizedPnlUsd": 1.0
+        });
+        vec![
+            mock("GET", "/api/v1/wallet/holdings")
+                .match_query(wallet_query(wallet, chain_id))
+                .with_status(200)
+                .with_body(json!({"status": "success", "message": "ok", "data": holdings}).to_string())
+                .create(),
+            mock("GET", "/api/v1/wallet/aggregated-pnl")
+                .match_query(wallet_query(wallet, chain_id))
+                .with_status(200)
+                .with_body(json!({"status": "success", "message": "ok", "data": pnl}).to_string())
+                .create(),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_portfolio_combines_chains_and_tolerates_failures() {
+        let wallet = "portfolio-wallet";
+        let _solana = mock_chain(wallet, ChainId::Solana, &[10.0, 5.5], 2.0);
+        let _base = mock_chain(wallet, ChainId::Base, &[4.5], -1.0);
+        let _failing = mock("GET", Matcher::Regex("^/api/v1/wallet/".to_string()))
+            .match_query(wallet_query(wallet, ChainId::Tron))
+            .with_status(503)
+            .create();
+
+        let portfolio = setup_client()
+            .get_wallet_portfolio(wallet, &[ChainId::Solana, ChainId::Tron, ChainId::Base, ChainId::Solana])
+            .await
+            .unwrap();
+
+        let chains: Vec<ChainId> = portfolio.chains.iter().map(|chain| chain.chain_id).collect();
+        assert_eq!(chains, vec![ChainId::Solana, ChainId::Base]);
+        assert_eq!(portfolio.chain(ChainId::Solana).unwrap().usd_value, number("15.5"));
+        assert_eq!(portfolio.total_usd_value, number("20"));
+        assert_eq!(portfolio.total_pnl_usd, number("1"));
+        assert_eq!(portfolio.total_invested_amount, number("200"));
+        assert!(!portfolio.is_complete());
+        assert_eq!(portfolio.failures.len(), 1);
+        assert!(matches!(portfolio.failures[0], (ChainId::Tron, VyperError::ServerError { status: 503, .. })));
+    }
+
+    #[tokio::test]
+    async fn test_portfolio_errors_when_every_chain_fails() {
+        let _failing = mock("GET", Matcher::Regex("^/api/v1/wallet/".to_string()))
+            .match_query(wallet_query("portfolio-unknown", ChainId::Blast))
+            .with_status(400)
+            .with_body(r#"{"message":"unknown wallet"}"#)
+            .create();
+
+        let result = setup_client().get_wallet_portfolio("portfolio-unknown", &[ChainId::Blast]).await;
+        assert!(matches!(result, Err(VyperError::ApiError { status: 400, .. })));
+    }
+}
@@ -0,0 +1,149 @@
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+/// Client-side token bucket that paces requests to stay under the API rate limit.
+///
+/// Clones share the same bucket, so a single limiter (or a cloned
+/// [`VyperClient`](crate::client::VyperClient)) can be handed to many tasks.
+/// Callers that exceed the budget wait in FIFO order rather than being
+/// rejected.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    capacity: f64,
+    refill_per_sec: f64,
+    endpoint_weights: Vec<(String, u32)>,
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    /// Allows `requests` per `period`, with a burst equal to `requests`.
+    pub fn new(requests: u32, period: Duration) -> Self {
+        let requests = requests.max(1);
+        let capacity = f64::from(requests);
+        Self {
+            capacity,
+            refill_per_sec: capacity / period.as_secs_f64().max(f64::EPSILON),
+            endpoint_weights: Vec::new(),
+            bucket: Arc::new(Mutex::new(Bucket {
+                tokens: capacity,
+                last_refill: Instant::now(),
+            })),
+        }
+    }
+
+    pub fn per_second(requests: u32) -> Self {
+        Self::new(requests, Duration::from_secs(1))
+    }
+
+    pub fn per_minute(requests: u32) -> Self {
+        Self::new(requests, Duration::from_secs(60))
+    }
+
+    /// Sets how many requests may be sent back-to-back before pacing kicks in.
+    pub fn with_burst(mut self, burst: u32) -> Self {
+        self.capacity = f64::from(burst.max(1));
+        self.bucket = Arc::new(Mutex::new(Bucket {
+            tokens: self.capacity,
+            last_refill: Instant::now(),
+        }));
+        self
+    }
+
+    /// Charges `weight` tokens for endpoints starting with `endpoint_prefix`,
+    /// e.g. `"/api/v1/token/pairs"`. The longest matching prefix wins and
+    /// unmatched endpoints cost one token.
+    pub fn with_endpoint_weight<S: Into<String>>(mut self, endpoint_prefix: S, weight: u32) -> Self {
+        self.endpoint_weights.push((endpoint_prefix.into(), weight));
+        self
+    }
+
+    pub fn weight_for(&self, endpoint: &str) -> u32 {
+        self.endpoint_weights
+            .iter()
+            .filter(|(prefix, _)| endpoint.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, weight)| *weight)
+            .unwrap_or(1)
+    }
+
+    /// Waits until the bucket holds enough tokens for `endpoint`, then takes them.
+    pub async fn acquire(&self, endpoint: &str) {
+        let weight = f64::from(self.weight_for(endpoint)).min(self.capacity);
+        if weight <= 0.0 {
+            return;
+        }
+
+        // The lock is held while sleeping so that waiters are served in order.
+        let mut bucket = self.bucket.lock().await;
+        self.refill(&mut bucket);
+        if bucket.tokens < weight {
+            let wait = (weight - bucket.tokens) / self.refill_per_sec;
+            tokio::time::sleep(Duration::from_secs_f64(wait)).await;
+            self.refill(&mut bucket);
+        }
+        bucket.tokens = (bucket.tokens - weight).max(0.0);
+    }
+
+    fn refill(&self, bucket: &mut Bucket) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        bucket.last_refill = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_burst_then_paced() {
+        let limiter = RateLimiter::per_second(2);
+        let start = Instant::now();
+
+        limiter.acquire("/api/v1/chain/ids").await;
+        limiter.acquire("/api/v1/chain/ids").await;
+        assert!(start.elapsed() < Duration::from_millis(10));
+
+        limiter.acquire("/api/v1/chain/ids").await;
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(500) && elapsed < Duration::from_millis(510));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_clones_share_state() {
+        let limiter = RateLimiter::per_minute(60).with_burst(1);
+        let other = limiter.clone();
+        let start = Instant::now();
+
+        limiter.acquire("/api/v1/token/ath").await;
+        other.acquire("/api/v1/token/ath").await;
+        assert!(start.elapsed() >= Duration::from_secs(1));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_endpoint_weights() {
+        let limiter = RateLimiter::per_second(10)
+            .with_endpoint_weight("/api/v1/token", 2)
+            .with_endpoint_weight("/api/v1/token/pairs", 5);
+
+        assert_eq!(limiter.weight_for("/api/v1/chain/ids"), 1);
+        assert_eq!(limiter.weight_for("/api/v1/token/market/abc"), 2);
+        assert_eq!(limiter.weight_for("/api/v1/token/pairs"), 5);
+
+        let start = Instant::now();
+        limiter.acquire("/api/v1/token/pairs").await;
+        limiter.acquire("/api/v1/token/pairs").await;
+        assert!(start.elapsed() < Duration::from_millis(10));
+        limiter.acquire("/api/v1/token/pairs").await;
+        assert!(start.elapsed() >= Duration::from_millis(500));
+    }
+}
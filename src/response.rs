@@ -0,0 +1,111 @@
+use std::time::Duration;
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+
+/// An API payload together with the metadata of the HTTP exchange that produced it.
+///
+/// Returned by the `*_with_meta` variants of the
+/// [`VyperClient`](crate::client::VyperClient) endpoints.
+#[derive(Debug, Clone)]
+pub struct Response<T> {
+    pub data: T,
+    /// HTTP status of the successful response.
+    pub status: StatusCode,
+    /// The `status` field of the API envelope, e.g. `"success"`.
+    pub api_status: String,
+    /// The `message` field of the API envelope.
+    pub message: String,
+    pub rate_limit: RateLimitInfo,
+    pub request_id: Option<String>,
+    /// Time spent on the successful attempt, from sending the request to
+    /// reading the full body.
+    pub latency: Duration,
+    /// Number of attempts made, including retries.
+    pub attempts: u32,
+    pub headers: HeaderMap,
+}
+
+impl<T> Response<T> {
+    pub fn into_data(self) -> T {
+        self.data
+    }
+
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Response<U> {
+        Response {
+            data: f(self.data),
+            status: self.status,
+            api_status: self.api_status,
+            message: self.message,
+            rate_limit: self.rate_limit,
+            request_id: self.request_id,
+            latency: self.latency,
+            attempts: self.attempts,
+            headers: self.headers,
+        }
+    }
+}
+
+/// A response as received from the transport, before the API envelope is parsed.
+#[derive(Debug, Clone)]
+pub(crate) struct RawResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: String,
+}
+
+/// Rate limit counters reported by the server, when present.
+///
+/// Both the `X-RateLimit-*` and the `RateLimit-*` header spellings are
+/// recognised. `reset` is passed through as sent by the server.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimitInfo {
+    pub limit: Option<u64>,
+    pub remaining: Option<u64>,
+    pub reset: Option<u64>,
+}
+
+impl RateLimitInfo {
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        Self {
+            limit: header_u64(headers, &["x-ratelimit-limit", "ratelimit-limit"]),
+            remaining: header_u64(headers, &["x-ratelimit-remaining", "ratelimit-remaining"]),
+            reset: header_u64(headers, &["x-ratelimit-reset", "ratelimit-reset"]),
+        }
+    }
+}
+
+pub(crate) fn request_id(headers: &HeaderMap) -> Option<String> {
+    ["x-request-id", "request-id"]
+        .iter()
+        .find_map(|name| headers.get(*name))
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+fn header_u64(headers: &HeaderMap, names: &[&str]) -> Option<u64> {
+    names
+        .iter()
+        .find_map(|name| headers.get(*name))
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limit_info_from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-RateLimit-Limit", "100".parse().unwrap());
+        headers.insert("X-RateLimit-Remaining", " 42 ".parse().unwrap());
+        headers.insert("RateLimit-Reset", "30".parse().unwrap());
+        headers.insert("X-Request-Id", "req-123".parse().unwrap());
+
+        let info = RateLimitInfo::from_headers(&headers);
+        assert_eq!(info, RateLimitInfo { limit: Some(100), remaining: Some(42), reset: Some(30) });
+        assert_eq!(request_id(&headers), Some("req-123".to_string()));
+
+        assert_eq!(RateLimitInfo::from_headers(&HeaderMap::new()), RateLimitInfo::default());
+    }
+}
@@ -0,0 +1,174 @@
+use std::time::Duration;
+use crate::errors::VyperError;
+
+/// Controls how [`VyperClient`](crate::client::VyperClient) retries failed requests.
+///
+/// Rate limited requests wait for the server supplied `Retry-After` delay when
+/// one is present; every other retryable failure backs off exponentially from
+/// `base_delay`, capped at `max_delay`.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one. `1` disables retries.
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    /// Upper bound for a single backoff. A `Retry-After` longer than this is
+    /// not waited for and the rate limit error is returned instead.
+    pub max_delay: Duration,
+    /// Fraction of each backoff (0.0 - 1.0) that is randomised to spread out
+    /// retries from concurrent callers.
+    pub jitter: f64,
+    pub retry_rate_limited: bool,
+    pub retry_server_errors: bool,
+    pub retry_connect_errors: bool,
+    pub retry_timeouts: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(250),
+            max_delay: Duration::from_secs(30),
+            jitter: 0.2,
+            retry_rate_limited: true,
+            retry_server_errors: true,
+            retry_connect_errors: true,
+            retry_timeouts: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_retryable(&self, error: &VyperError) -> bool {
+        match error {
+            VyperError::RateLimitError { .. } => self.retry_rate_limited,
+            VyperError::ServerError { .. } => self.retry_server_errors,
+            VyperError::HttpError(e) if e.is_timeout() => self.retry_timeouts,
+            VyperError::HttpError(e) if e.is_connect() => self.retry_connect_errors,
+            _ => false,
+        }
+    }
+
+    /// Returns how long to wait before the next attempt, or `None` if the
+    /// failed `attempt` (1-based) should not be retried.
+    pub fn next_delay(&self, attempt: u32, error: &VyperError) -> Option<Duration> {
+        if attempt >= self.max_attempts || !self.is_retryable(error) {
+            return None;
+        }
+
+        if let VyperError::RateLimitError { retry_after: Some(secs), .. } = error {
+            let delay = Duration::try_from_secs_f64(*secs).ok()?;
+            return if delay <= self.max_delay { Some(delay) } else { None };
+        }
+
+        Some(self.backoff(attempt))
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        exponential_backoff(self.base_delay, self.max_delay, self.jitter, attempt)
+    }
+}
+
+/// `base_delay * 2^(attempt - 1)`, capped at `max_delay`, with up to `jitter`
+/// (0.0 - 1.0) of it randomly taken off.
+pub(crate) fn exponential_backoff(base_delay: Duration, max_delay: Duration, jitter: f64, attempt: u32) -> Duration {
+    let exponent = attempt.saturating_sub(1).min(31);
+    let delay = base_delay
+        .checked_mul(1u32 << exponent)
+        .unwrap_or(max_delay)
+        .min(max_delay);
+
+    let jitter = jitter.clamp(0.0, 1.0);
+    if jitter > 0.0 {
+        delay.mul_f64(1.0 - jitter * fastrand::f64())
+    } else {
+        delay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server_error() -> VyperError {
+        VyperError::ServerError {
+            message: "boom".to_string(),
+            status: 503,
+            body: None,
+        }
+    }
+
+    #[test]
+    fn test_backoff_grows_and_caps() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(500),
+            jitter: 0.0,
+            ..Default::default()
+        };
+
+        assert_eq!(policy.next_delay(1, &server_error()), Some(Duration::from_millis(100)));
+        assert_eq!(policy.next_delay(2, &server_error()), Some(Duration::from_millis(200)));
+        assert_eq!(policy.next_delay(3, &server_error()), Some(Duration::from_millis(400)));
+        assert_eq!(policy.next_delay(4, &server_error()), Some(Duration::from_millis(500)));
+        assert_eq!(policy.next_delay(10, &server_error()), None);
+    }
+
+    #[test]
+    fn test_jitter_stays_within_bounds() {
+        let policy = RetryPolicy {
+            base_delay: Duration::from_millis(100),
+            jitter: 0.5,
+            ..Default::default()
+        };
+
+        for _ in 0..100 {
+            let delay = policy.next_delay(1, &server_error()).unwrap();
+            assert!(delay >= Duration::from_millis(50) && delay <= Duration::from_millis(100));
+        }
+    }
+
+    #[test]
+    fn test_honours_retry_after() {
+        let policy = RetryPolicy::default();
+        let error = VyperError::RateLimitError {
+            message: "slow down".to_string(),
+            retry_after: Some(2.5),
+        };
+        assert_eq!(policy.next_delay(1, &error), Some(Duration::from_millis(2500)));
+
+        let error = VyperError::RateLimitError {
+            message: "slow down".to_string(),
+            retry_after: Some(3600.0),
+        };
+        assert_eq!(policy.next_delay(1, &error), None);
+    }
+
+    #[test]
+    fn test_non_retryable_errors() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.next_delay(1, &VyperError::AuthenticationError("nope".to_string())), None);
+        let bad_request = VyperError::ApiError {
+            message: "bad".to_string(),
+            status: 400,
+            body: None,
+        };
+        assert_eq!(policy.next_delay(1, &bad_request), None);
+
+        let policy = RetryPolicy {
+            retry_server_errors: false,
+            ..Default::default()
+        };
+        assert_eq!(policy.next_delay(1, &server_error()), None);
+        assert_eq!(RetryPolicy::none().next_delay(1, &server_error()), None);
+    }
+}
@@ -0,0 +1,261 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::client::VyperClient;
+use crate::errors::VyperError;
+use crate::types::{number_to_f64, timestamp_millis, ChainId, Timestamp, TokenSearchResult};
+
+/// Weights used to order the results of
+/// [`VyperClient::search_tokens_across_chains`].
+///
+/// A result's score is the sum of
+/// - `exact_symbol_match` if its symbol equals the search criteria (ignoring case),
+/// - `liquidity * log10(1 + token_liquidity_usd)`,
+/// - `volume * log10(1 + volume_usd)`,
+/// - `age * log10(1 + age in days)`.
+///
+/// Liquidity, volume and age are log-scaled so that a single outlier cannot
+/// drown out the other signals. Set a weight to `0.0` to ignore it, or make
+/// `age` negative to favour new tokens.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchRanking {
+    pub exact_symbol_match: f64,
+    pub liquidity: f64,
+    pub volume: f64,
+    pub age: f64,
+}
+
+impl Default for SearchRanking {
+    fn default() -> Self {
+        Self {
+            exact_symbol_match: 10.0,
+            liquidity: 1.0,
+            volume: 1.0,
+            age: 0.5,
+        }
+    }
+}
+
+impl SearchRanking {
+    pub fn score(&self, result: &TokenSearchResult, criteria: &str) -> f64 {
+        let exact = if result.symbol.eq_ignore_ascii_case(criteria.trim()) { 1.0 } else { 0.0 };
+        self.exact_symbol_match * exact
+            + self.liquidity * log_scale(number_to_f64(result.token_liquidity_usd))
+            + self.volume * log_scale(number_to_f64(result.volume_usd))
+            + self.age * log_scale(age_days(result.created_timestamp))
+    }
+}
+
+fn log_scale(value: f64) -> f64 {
+    if value.is_finite() && value > 0.0 {
+        value.ln_1p() / std::f64::consts::LN_10
+    } else {
+        0.0
+    }
+}
+
+fn age_days(created: Timestamp) -> f64 {
+    let created_secs = timestamp_millis(created) as f64 / 1000.0;
+    let now_secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|now| now.as_secs_f64())
+        .unwrap_or(0.0);
+    ((now_secs - created_secs) / 86_400.0).max(0.0)
+}
+
+#[derive(Debug)]
+pub struct RankedSearchResult {
+    pub result: TokenSearchResult,
+    pub score: f64,
+}
+
+/// Results of [`VyperClient::search_tokens_across_chains`].
+#[derive(Debug)]
+pub struct MultiChainSearch {
+    /// Unique tokens, best score first.
+    pub results: Vec<RankedSearchResult>,
+    /// Chains whose search failed, with the error for each.
+    pub failures: Vec<(ChainId, VyperError)>,
+}
+
+impl MultiChainSearch {
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    pub fn into_results(self) -> Vec<TokenSearchResult> {
+        self.results.into_iter().map(|ranked| ranked.result).collect()
+    }
+}
+
+impl VyperClient {
+    /// Runs [`search_tokens`](VyperClient::search_tokens) on every chain in
+    /// `chains` concurrently and merges the results. An empty `chains` means
+    /// every chain reported by [`get_chain_ids`](VyperClient::get_chain_ids).
+    ///
+    /// Results are de-duplicated by `(chain_id, token_mint)`, keeping the
+    /// market with the most liquidity, and sorted by `ranking`. Chains that
+    /// fail are reported in [`MultiChainSearch::failures`]; an error is only
+    /// returned when the chain list cannot be fetched or every chain failed.
+    pub async fn search_tokens_across_chains(
+        &self,
+        criteria: &str,
+        chains: &[ChainId],
+        ranking: &SearchRanking,
+    ) -> Result<MultiChainSearch, VyperError> {
+        let responses = self
+            .fan_out_chains(chains, |chain_id| self.search_tokens(criteria, Some(chain_id)))
+            .await?;
+
+        let mut unique: HashMap<(ChainId, String), TokenSearchResult> = HashMap::new();
+        for result in responses.succeeded.into_iter().flat_map(|(_, results)| results) {
+            let key = (result.chain_id, result.token_mint.clone());
+            match unique.get(&key) {
+                Some(existing) if existing.token_liquidity_usd >= result.token_liquidity_usd => {}
+                _ => {
+                    unique.insert(key, result);
+                }
+            }
+        }
+
+        let mut results: Vec<RankedSearchResult> = unique
+            .into_values()
+            .map(|result| RankedSearchResult {
+                score: ranking.score(&result, criteria),
+                result,
+            })
+            .collect();
+        results.sort_by(compare_ranked);
+        Ok(MultiChainSearch {
+            results,
+            failures: responses.failures,
+        })
+    }
+}
+
+/// Best score first; ties go to the more liquid token, then a stable order.
+fn compare_ranked(a: &RankedSearchResult, b: &RankedSearchResult) -> Ordering {
+    b.score
+        .total_cmp(&a.score)
+        .then_with(|| liquidity(&b.result).total_cmp(&liquidity(&a.result)))
+        .then_with(|| a.result.chain_id.id().cmp(&b.result.chain_id.id()))
+        .then_with(|| a.result.token_mint.cmp(&b.result.token_mint))
+}
+
+fn liquidity(result: &TokenSearchResult) -> f64 {
+    number_to_f64(result.token_liquidity_usd)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chains::setup_client;
+    use crate::types::Number;
+    use mockito::{mock, Matcher};
+    use serde_json::json;
+
+    fn search_result_json(chain_id: ChainId, mint: &str, symbol: &str, liquidity: f64, volume: f64) -> serde_json::Value {
+        json!({
+            "chainId": chain_id.id(),
+            "marketId": format!("{}-market-{}", mint, liquidity),
+            "createdTimestamp": 1609459200,
+            "name": symbol,
+            "symbol": symbol,
+            "tokenMint": mint,
+            "tokenType": "meme",
+            "percentChange24h": 0.0,
+            "pooledAsset": 0.0,
+            "tokenLiquidityUsd": liquidity,
+            "tokenMarketCapUsd": 0.0,
+            "tokenPriceUsd": 1.0,
+            "volumeUsd": volume
+        })
+    }
+
+    fn mock_search(criteria: &str, chain_id: ChainId, status: usize, data: serde_json::Value) -> mockito::Mock {
+        mock("GET", "/api/v1/token/search")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("criteria".into(), criteria.into()),
+                Matcher::UrlEncoded("chainID".into(), chain_id.id().to_string()),
+            ]))
+            .with_status(status)
+            .with_body(json!({"status": "success", "message": "ok", "data": data}).to_string())
+            .create()
+    }
+
+    #[tokio::test]
+    async fn test_merges_deduplicates_and_ranks() {
+        let _solana = mock_search(
+            "pepe-fanout",
+            ChainId::Solana,
+            200,
+            json!([
+                search_result_json(ChainId::Solana, "mint-a", "PEPE2", 1_000_000.0, 500_000.0),
+                search_result_json(ChainId::Solana, "mint-b", "PEPE-FANOUT", 10.0, 0.0),
+                search_result_json(ChainId::Solana, "mint-a", "PEPE2", 5_000_000.0, 500_000.0),
+            ]),
+        );
+        let _base = mock_search(
+            "pepe-fanout",
+            ChainId::Base,
+            200,
+            json!([search_result_json(ChainId::Base, "mint-a", "PEPE3", 100.0, 10.0)]),
+        );
+        let _tron = mock_search("pepe-fanout", ChainId::Tron, 503, json!(null));
+
+        let search = setup_client()
+            .search_tokens_across_chains(
+                "pepe-fanout",
+                &[ChainId::Solana, ChainId::Base, ChainId::Tron],
+                &SearchRanking::default(),
+            )
+            .await
+            .unwrap();
+
+        let order: Vec<(ChainId, &str)> = search
+            .results
+            .iter()
+            .map(|ranked| (ranked.result.chain_id, ranked.result.token_mint.as_str()))
+            .collect();
+        assert_eq!(
+            order,
+            vec![(ChainId::Solana, "mint-a"), (ChainId::Solana, "mint-b"), (ChainId::Base, "mint-a")]
+        );
+        assert_eq!(search.results[0].result.token_liquidity_usd, Number::from(5_000_000));
+        assert_eq!(search.failures.len(), 1);
+        assert!(matches!(search.failures[0].0, ChainId::Tron));
+    }
+
+    #[test]
+    fn test_ranking_weights() {
+        let exact: TokenSearchResult =
+            serde_json::from_value(search_result_json(ChainId::Solana, "a", "WIF", 10.0, 10.0)).unwrap();
+        let liquid: TokenSearchResult =
+            serde_json::from_value(search_result_json(ChainId::Solana, "b", "WIFHAT", 1_000_000.0, 10.0)).unwrap();
+
+        let default = SearchRanking::default();
+        assert!(default.score(&exact, "wif") > default.score(&liquid, "wif"));
+
+        let liquidity_only = SearchRanking {
+            exact_symbol_match: 0.0,
+            liquidity: 1.0,
+            volume: 0.0,
+            age: 0.0,
+        };
+        assert!(liquidity_only.score(&liquid, "wif") > liquidity_only.score(&exact, "wif"));
+        assert!((liquidity_only.score(&liquid, "wif") - 1_000_001.0f64.log10()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_age_detects_milliseconds() {
+        let seconds: TokenSearchResult =
+            serde_json::from_value(search_result_json(ChainId::Solana, "a", "OLD", 1.0, 1.0)).unwrap();
+        let mut millis = search_result_json(ChainId::Solana, "a", "OLD", 1.0, 1.0);
+        millis["createdTimestamp"] = json!(1_609_459_200_000i64);
+        let millis: TokenSearchResult = serde_json::from_value(millis).unwrap();
+
+        let seconds = age_days(seconds.created_timestamp);
+        assert!((seconds - age_days(millis.created_timestamp)).abs() < 1.0);
+        assert!(seconds > 365.0);
+    }
+}
@@ -1,5 +1,110 @@
-use serde::{Deserialize, Serialize};
-use std::vec;
+use serde::de::{SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::convert::Infallible;
+use std::str::FromStr;
+use std::ops::{Bound, RangeBounds};
+use thiserror::Error;
+use crate::errors::ParamsError;
+
+/// Numeric type of prices, amounts, liquidity, market caps and USD values in
+/// API responses. Percentages stay `f64`.
+///
+/// This is `f64` unless the `decimal` feature is enabled, in which case it is
+/// [`rust_decimal::Decimal`] and JSON numbers are parsed exactly, without
+/// going through `f64`. Use [`number_to_f64`] where a float is needed either way.
+#[cfg(not(feature = "decimal"))]
+pub type Number = f64;
+
+/// Numeric type of prices, amounts, liquidity, market caps and USD values in
+/// API responses. Percentages stay `f64`.
+///
+/// With the `decimal` feature this is [`rust_decimal::Decimal`], parsed
+/// exactly from the JSON number and serialized as a string. Use
+/// [`number_to_f64`] where a float is needed either way.
+#[cfg(feature = "decimal")]
+pub type Number = rust_decimal::Decimal;
+
+/// Converts a [`Number`] to `f64`, e.g. for scoring or charting. Lossy with
+/// the `decimal` feature.
+#[cfg(not(feature = "decimal"))]
+pub fn number_to_f64(value: Number) -> f64 {
+    value
+}
+
+/// Converts a [`Number`] to `f64`, e.g. for scoring or charting. Lossy with
+/// the `decimal` feature.
+#[cfg(feature = "decimal")]
+pub fn number_to_f64(value: Number) -> f64 {
+    use rust_decimal::prelude::ToPrimitive;
+    value.to_f64().unwrap_or(f64::NAN)
+}
+
+/// A point in time reported by the API.
+///
+/// The API is not consistent about units: some fields arrive as Unix
+/// seconds, others as Unix milliseconds. A raw value of 10^12 or more (any
+/// time after September 2001 in milliseconds, or after the year 33658 in
+/// seconds) is treated as milliseconds and anything smaller as seconds; see
+/// [`unix_millis`].
+///
+/// Without the `chrono` feature this is the raw `i64` exactly as sent; use
+/// [`timestamp_millis`] to normalise it.
+#[cfg(not(feature = "chrono"))]
+pub type Timestamp = i64;
+
+/// A point in time reported by the API.
+///
+/// The API is not consistent about units: some fields arrive as Unix
+/// seconds, others as Unix milliseconds. A raw value of 10^12 or more (any
+/// time after September 2001 in milliseconds, or after the year 33658 in
+/// seconds) is treated as milliseconds and anything smaller as seconds; see
+/// [`unix_millis`].
+///
+/// With the `chrono` feature this is a `DateTime<Utc>`, converted with that
+/// rule when deserializing and serialized back as Unix milliseconds.
+#[cfg(feature = "chrono")]
+pub type Timestamp = chrono::DateTime<chrono::Utc>;
+
+const MILLIS_THRESHOLD: i64 = 1_000_000_000_000;
+
+/// Normalises a raw API timestamp, in seconds or milliseconds, to Unix milliseconds.
+pub fn unix_millis(raw: i64) -> i64 {
+    if raw.saturating_abs() >= MILLIS_THRESHOLD {
+        raw
+    } else {
+        raw * 1000
+    }
+}
+
+/// Unix milliseconds of a [`Timestamp`], whichever representation it has.
+#[cfg(not(feature = "chrono"))]
+pub fn timestamp_millis(value: Timestamp) -> i64 {
+    unix_millis(value)
+}
+
+/// Unix milliseconds of a [`Timestamp`], whichever representation it has.
+#[cfg(feature = "chrono")]
+pub fn timestamp_millis(value: Timestamp) -> i64 {
+    value.timestamp_millis()
+}
+
+#[cfg(feature = "chrono")]
+mod timestamp {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &Timestamp, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(value.timestamp_millis())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Timestamp, D::Error> {
+        let raw = i64::deserialize(deserializer)?;
+        chrono::DateTime::from_timestamp_millis(unix_millis(raw))
+            .ok_or_else(|| serde::de::Error::custom(format!("timestamp out of range: {}", raw)))
+    }
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct APIResponse<T> {
@@ -8,26 +113,258 @@ pub struct APIResponse<T> {
     pub data: T,
 }
 
+/// A blockchain supported by the Vyper API.
+///
+/// Serialized as the numeric id the API uses; displayed and parsed by name
+/// (`"solana"`, `"base"`, ...). Ids without a named variant are kept in
+/// [`ChainId::Unknown`], which compares equal to the named variant with the
+/// same id.
+#[derive(Debug, Clone, Copy)]
+pub enum ChainId {
+    Solana,
+    Tron,
+    Ethereum,
+    Base,
+    Arbitrum,
+    Bsc,
+    Blast,
+    Unknown(i32),
+}
+
+impl ChainId {
+    pub const KNOWN: [ChainId; 7] = [
+        ChainId::Solana,
+        ChainId::Tron,
+        ChainId::Ethereum,
+        ChainId::Base,
+        ChainId::Arbitrum,
+        ChainId::Bsc,
+        ChainId::Blast,
+    ];
+
+    pub fn id(self) -> i32 {
+        match self {
+            ChainId::Solana => 900,
+            ChainId::Tron => 1000,
+            ChainId::Ethereum => 1,
+            ChainId::Base => 8453,
+            ChainId::Arbitrum => 42161,
+            ChainId::Bsc => 56,
+            ChainId::Blast => 81457,
+            ChainId::Unknown(id) => id,
+        }
+    }
+
+    pub fn name(self) -> Option<&'static str> {
+        match ChainId::from(self.id()) {
+            ChainId::Solana => Some("solana"),
+            ChainId::Tron => Some("tron"),
+            ChainId::Ethereum => Some("ethereum"),
+            ChainId::Base => Some("base"),
+            ChainId::Arbitrum => Some("arbitrum"),
+            ChainId::Bsc => Some("bsc"),
+            ChainId::Blast => Some("blast"),
+            ChainId::Unknown(_) => None,
+        }
+    }
+}
+
+impl From<i32> for ChainId {
+    fn from(id: i32) -> Self {
+        ChainId::KNOWN
+            .iter()
+            .copied()
+            .find(|chain| chain.id() == id)
+            .unwrap_or(ChainId::Unknown(id))
+    }
+}
+
+impl From<ChainId> for i32 {
+    fn from(chain: ChainId) -> Self {
+        chain.id()
+    }
+}
+
+impl PartialEq for ChainId {
+    fn eq(&self, other: &Self) -> bool {
+        self.id() == other.id()
+    }
+}
+
+impl Eq for ChainId {}
+
+impl Hash for ChainId {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id().hash(state);
+    }
+}
+
+impl fmt::Display for ChainId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.name() {
+            Some(name) => f.write_str(name),
+            None => write!(f, "{}", self.id()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("unknown chain: {0}")]
+pub struct ParseChainIdError(pub String);
+
+impl FromStr for ChainId {
+    type Err = ParseChainIdError;
+
+    /// Accepts a chain name (case-insensitive) or a numeric id.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Ok(id) = s.parse::<i32>() {
+            return Ok(ChainId::from(id));
+        }
+        ChainId::KNOWN
+            .iter()
+            .copied()
+            .find(|chain| chain.name().is_some_and(|name| name.eq_ignore_ascii_case(s)))
+            .ok_or_else(|| ParseChainIdError(s.to_string()))
+    }
+}
+
+impl Serialize for ChainId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i32(self.id())
+    }
+}
+
+impl<'de> Deserialize<'de> for ChainId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        i32::deserialize(deserializer).map(ChainId::from)
+    }
+}
+
+/// Time window for market statistics such as volume and price change.
+///
+/// Only the daily window is modelled so far. Pass any other window the
+/// [API](https://docs.vyper.trade/) accepts through [`Interval::Other`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Interval {
+    OneDay,
+    Other(String),
+}
+
+impl Interval {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Interval::OneDay => "1d",
+            Interval::Other(value) => value,
+        }
+    }
+}
+
+impl From<&str> for Interval {
+    fn from(s: &str) -> Self {
+        match s {
+            "1d" => Interval::OneDay,
+            other => Interval::Other(other.to_string()),
+        }
+    }
+}
+
+impl FromStr for Interval {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Interval::from(s))
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for Interval {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Interval {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(|s| Interval::from(s.as_str()))
+    }
+}
+
+/// Sort order for [`TokenPairsParams::sorting`].
+///
+/// No sort keys are modelled yet. Pass the key from the
+/// [API documentation](https://docs.vyper.trade/) through
+/// [`PairSorting::Other`], e.g. `PairSorting::from("volume")`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PairSorting {
+    Other(String),
+}
+
+impl PairSorting {
+    pub fn as_str(&self) -> &str {
+        match self {
+            PairSorting::Other(value) => value,
+        }
+    }
+}
+
+impl From<&str> for PairSorting {
+    fn from(s: &str) -> Self {
+        PairSorting::Other(s.to_string())
+    }
+}
+
+impl FromStr for PairSorting {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(PairSorting::from(s))
+    }
+}
+
+impl fmt::Display for PairSorting {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for PairSorting {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for PairSorting {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(|s| PairSorting::from(s.as_str()))
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct WalletAggregatedPnL {
     #[serde(rename = "investedAmount")]
-    pub invested_amount: f64,
+    pub invested_amount: Number,
     #[serde(rename = "pnlPercent")]
     pub pnl_percent: f64,
     #[serde(rename = "pnlUsd")]
-    pub pnl_usd: f64,
+    pub pnl_usd: Number,
     #[serde(rename = "soldAmount")]
-    pub sold_amount: f64,
+    pub sold_amount: Number,
     #[serde(rename = "tokensTraded")]
     pub tokens_traded: i32,
     #[serde(rename = "totalPnlPercent")]
     pub total_pnl_percent: f64,
     #[serde(rename = "totalPnlUsd")]
-    pub total_pnl_usd: f64,
+    pub total_pnl_usd: Number,
     #[serde(rename = "unrealizedPnlPercent")]
     pub unrealized_pnl_percent: f64,
     #[serde(rename = "unrealizedPnlUsd")]
-    pub unrealized_pnl_usd: f64,
+    pub unrealized_pnl_usd: Number,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -35,31 +372,32 @@ pub struct WalletHolding {
     #[serde(rename = "marketId")]
     pub market_id: String,
     #[serde(rename = "tokenHoldings")]
-    pub token_holdings: f64,
+    pub token_holdings: Number,
     #[serde(rename = "tokenSymbol")]
     pub token_symbol: String,
     #[serde(rename = "usdValue")]
-    pub usd_value: f64,
+    pub usd_value: Number,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct WalletPnL {
     #[serde(rename = "holderSince")]
-    pub holder_since: i64,
+    #[cfg_attr(feature = "chrono", serde(with = "timestamp"))]
+    pub holder_since: Timestamp,
     #[serde(rename = "investedAmount")]
-    pub invested_amount: f64,
+    pub invested_amount: Number,
     #[serde(rename = "investedTxns")]
     pub invested_txns: i32,
     #[serde(rename = "pnlPercent")]
     pub pnl_percent: f64,
     #[serde(rename = "pnlUsd")]
-    pub pnl_usd: f64,
+    pub pnl_usd: Number,
     #[serde(rename = "remainingTokens")]
-    pub remaining_tokens: f64,
+    pub remaining_tokens: Number,
     #[serde(rename = "remainingUsd")]
-    pub remaining_usd: f64,
+    pub remaining_usd: Number,
     #[serde(rename = "soldAmount")]
-    pub sold_amount: f64,
+    pub sold_amount: Number,
     #[serde(rename = "soldTxns")]
     pub sold_txns: i32,
 }
@@ -67,21 +405,21 @@ pub struct WalletPnL {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TopTrader {
     #[serde(rename = "investedAmount_tokens")]
-    pub invested_amount_tokens: f64,
+    pub invested_amount_tokens: Number,
     #[serde(rename = "investedAmount_usd")]
-    pub invested_amount_usd: f64,
+    pub invested_amount_usd: Number,
     #[serde(rename = "investedTxns")]
     pub invested_txns: i32,
     #[serde(rename = "pnlUsd")]
-    pub pnl_usd: f64,
+    pub pnl_usd: Number,
     #[serde(rename = "remainingTokens")]
-    pub remaining_tokens: f64,
+    pub remaining_tokens: Number,
     #[serde(rename = "remainingUsd")]
-    pub remaining_usd: f64,
+    pub remaining_usd: Number,
     #[serde(rename = "soldAmountTokens")]
-    pub sold_amount_tokens: f64,
+    pub sold_amount_tokens: Number,
     #[serde(rename = "soldAmountUsd")]
-    pub sold_amount_usd: f64,
+    pub sold_amount_usd: Number,
     #[serde(rename = "soldTxns")]
     pub sold_txns: i32,
     #[serde(rename = "walletAddress")]
@@ -93,11 +431,12 @@ pub struct TopTrader {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TokenSearchResult {
     #[serde(rename = "chainId")]
-    pub chain_id: i32,
+    pub chain_id: ChainId,
     #[serde(rename = "marketId")]
     pub market_id: String,
     #[serde(rename = "createdTimestamp")]
-    pub created_timestamp: i64,
+    #[cfg_attr(feature = "chrono", serde(with = "timestamp"))]
+    pub created_timestamp: Timestamp,
     pub name: String,
     pub symbol: String,
     #[serde(rename = "tokenMint")]
@@ -107,15 +446,15 @@ pub struct TokenSearchResult {
     #[serde(rename = "percentChange24h")]
     pub percent_change_24h: f64,
     #[serde(rename = "pooledAsset")]
-    pub pooled_asset: f64,
+    pub pooled_asset: Number,
     #[serde(rename = "tokenLiquidityUsd")]
-    pub token_liquidity_usd: f64,
+    pub token_liquidity_usd: Number,
     #[serde(rename = "tokenMarketCapUsd")]
-    pub token_market_cap_usd: f64,
+    pub token_market_cap_usd: Number,
     #[serde(rename = "tokenPriceUsd")]
-    pub token_price_usd: f64,
+    pub token_price_usd: Number,
     #[serde(rename = "volumeUsd")]
-    pub volume_usd: f64,
+    pub volume_usd: Number,
     pub image: Option<String>,
     pub telegram: Option<String>,
     pub twitter: Option<String>,
@@ -125,11 +464,11 @@ pub struct TokenSearchResult {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TokenMarket {
     #[serde(rename = "marketCapUsd")]
-    pub market_cap_usd: f64,
+    pub market_cap_usd: Number,
     #[serde(rename = "marketID")]
     pub market_id: String,
     #[serde(rename = "tokenLiquidityUsd")]
-    pub token_liquidity_usd: f64,
+    pub token_liquidity_usd: Number,
     #[serde(rename = "tokenType")]
     pub token_type: String,
 }
@@ -154,22 +493,52 @@ pub struct TokenHolder {
     #[serde(rename = "percentOwned")]
     pub percent_owned: f64,
     #[serde(rename = "tokenHoldings")]
-    pub token_holdings: f64,
+    pub token_holdings: Number,
     #[serde(rename = "usdHoldings")]
-    pub usd_holdings: f64,
+    pub usd_holdings: Number,
     #[serde(rename = "walletAddress")]
     pub wallet_address: String,
     #[serde(rename = "walletTag")]
     pub wallet_tag: Option<String>,
 }
 
+/// One page of holders of a market, returned by
+/// [`VyperClient::get_token_holders`](crate::client::VyperClient::get_token_holders).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TokenHolders {
+    pub holders: Vec<TokenHolder>,
+    /// Number of holders across all pages.
+    #[serde(rename = "total_holders", alias = "totalHolders")]
+    pub total_holders: i32,
+}
+
+/// Paging for [`VyperClient::get_token_holders_page`](crate::client::VyperClient::get_token_holders_page).
+/// Unset fields use the server defaults.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TokenHoldersParams {
+    pub page: Option<i32>,
+    /// Holders per page.
+    pub limit: Option<i32>,
+}
+
+impl TokenHoldersParams {
+    pub fn validate(&self) -> Result<(), ParamsError> {
+        match (self.page, self.limit) {
+            (Some(page), _) if page < 1 => Err(ParamsError::InvalidPage(page)),
+            (_, Some(limit)) if limit < 1 => Err(ParamsError::InvalidLimit(limit)),
+            _ => Ok(()),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TokenATH {
     #[serde(rename = "marketCapUsd")]
-    pub market_cap_usd: f64,
-    pub timestamp: i64,
+    pub market_cap_usd: Number,
+    #[cfg_attr(feature = "chrono", serde(with = "timestamp"))]
+    pub timestamp: Timestamp,
     #[serde(rename = "tokenLiquidityUsd")]
-    pub token_liquidity_usd: f64,
+    pub token_liquidity_usd: Number,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -178,8 +547,9 @@ pub struct MigrationState {
     pub duration_minutes: i32,
     pub makers: i32,
     #[serde(rename = "migrationTimestamp")]
-    pub migration_timestamp: i64,
-    pub volume: f64,
+    #[cfg_attr(feature = "chrono", serde(with = "timestamp"))]
+    pub migration_timestamp: Timestamp,
+    pub volume: Number,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -190,19 +560,20 @@ pub struct TokenPair {
     #[serde(rename = "buyTxnCount")]
     pub buy_txn_count: i32,
     #[serde(rename = "chainId")]
-    pub chain_id: i32,
+    pub chain_id: ChainId,
     #[serde(rename = "contractCreator")]
     pub contract_creator: String,
     #[serde(rename = "createdTimestamp")]
-    pub created_timestamp: i64,
+    #[cfg_attr(feature = "chrono", serde(with = "timestamp"))]
+    pub created_timestamp: Timestamp,
     pub description: Option<String>,
     #[serde(rename = "freezeAuthority")]
     pub freeze_authority: Option<bool>,
     pub image: Option<String>,
     #[serde(rename = "initialAssetLiquidity")]
-    pub initial_asset_liquidity: f64,
+    pub initial_asset_liquidity: Number,
     #[serde(rename = "initialUsdLiquidity")]
-    pub initial_usd_liquidity: f64,
+    pub initial_usd_liquidity: Number,
     #[serde(rename = "isMigrated")]
     pub is_migrated: Option<bool>,
     #[serde(rename = "lpBurned")]
@@ -221,9 +592,9 @@ pub struct TokenPair {
     pub mint_authority: Option<bool>,
     pub name: String,
     #[serde(rename = "pooledAsset")]
-    pub pooled_asset: f64,
+    pub pooled_asset: Number,
     #[serde(rename = "pooledToken")]
-    pub pooled_token: f64,
+    pub pooled_token: Number,
     #[serde(rename = "priceChangePercent")]
     pub price_change_percent: f64,
     #[serde(rename = "sellTxnCount")]
@@ -231,32 +602,32 @@ pub struct TokenPair {
     pub symbol: String,
     pub telegram: Option<String>,
     #[serde(rename = "tokenLiquidityAsset")]
-    pub token_liquidity_asset: f64,
+    pub token_liquidity_asset: Number,
     #[serde(rename = "tokenLiquidityUsd")]
-    pub token_liquidity_usd: f64,
+    pub token_liquidity_usd: Number,
     #[serde(rename = "tokenMarketCapAsset")]
-    pub token_market_cap_asset: f64,
+    pub token_market_cap_asset: Number,
     #[serde(rename = "tokenMarketCapUsd")]
-    pub token_market_cap_usd: f64,
+    pub token_market_cap_usd: Number,
     #[serde(rename = "tokenMint")]
     pub token_mint: String,
     #[serde(rename = "tokenPriceAsset")]
-    pub token_price_asset: f64,
+    pub token_price_asset: Number,
     #[serde(rename = "tokenPriceUsd")]
-    pub token_price_usd: f64,
+    pub token_price_usd: Number,
     #[serde(rename = "tokenType")]
     pub token_type: String,
     #[serde(rename = "top10HoldingPercent")]
     pub top10_holding_percent: f64,
     #[serde(rename = "totalSupply")]
-    pub total_supply: f64,
+    pub total_supply: Number,
     #[serde(rename = "transactionCount")]
     pub transaction_count: i32,
     pub twitter: Option<String>,
     #[serde(rename = "volumeAsset")]
-    pub volume_asset: f64,
+    pub volume_asset: Number,
     #[serde(rename = "volumeUsd")]
-    pub volume_usd: f64,
+    pub volume_usd: Number,
     pub website: Option<String>,
 }
 
@@ -281,197 +652,599 @@ pub struct ChainAction {
     #[serde(rename = "actionType")]
     pub action_type: String,
     #[serde(rename = "tokenAmount")]
-    pub token_amount: f64,
+    pub token_amount: Number,
     #[serde(rename = "assetAmount")]
-    pub asset_amount: f64,
+    pub asset_amount: Number,
     #[serde(rename = "tokenPriceUsd")]
-    pub token_price_usd: f64,
+    pub token_price_usd: Number,
     #[serde(rename = "tokenPriceAsset")]
-    pub token_price_asset: f64,
+    pub token_price_asset: Number,
     #[serde(rename = "swapTotalUsd")]
-    pub swap_total_usd: Option<f64>,
+    pub swap_total_usd: Option<Number>,
     #[serde(rename = "swapTotalAsset")]
-    pub swap_total_asset: Option<f64>,
+    pub swap_total_asset: Option<Number>,
     #[serde(rename = "tokenMarketCapAsset")]
-    pub token_market_cap_asset: f64,
+    pub token_market_cap_asset: Number,
     #[serde(rename = "tokenMarketCapUsd")]
-    pub token_market_cap_usd: f64,
+    pub token_market_cap_usd: Number,
     #[serde(rename = "tokenLiquidityAsset")]
-    pub token_liquidity_asset: f64,
+    pub token_liquidity_asset: Number,
     #[serde(rename = "tokenLiquidityUsd")]
-    pub token_liquidity_usd: f64,
+    pub token_liquidity_usd: Number,
     #[serde(rename = "pooledToken")]
-    pub pooled_token: f64,
+    pub pooled_token: Number,
     #[serde(rename = "pooledAsset")]
-    pub pooled_asset: f64,
+    pub pooled_asset: Number,
     #[serde(rename = "actionTimestamp")]
-    pub action_timestamp: i64,
+    #[cfg_attr(feature = "chrono", serde(with = "timestamp"))]
+    pub action_timestamp: Timestamp,
     #[serde(rename = "bondingCurvePercentage")]
     pub bonding_curve_percentage: Option<f64>,
     #[serde(rename = "botUsed")]
     pub bot_used: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Filters for [`VyperClient::get_token_pairs`](crate::client::VyperClient::get_token_pairs).
+///
+/// Construct it directly or with [`TokenPairsParams::builder`], which checks
+/// the ranges before any request is made. Unset fields are omitted from the
+/// query string.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TokenPairsParams {
-    #[serde(rename = "atLeastOneSocial")]
+    #[serde(rename = "atLeastOneSocial", skip_serializing_if = "Option::is_none")]
     pub at_least_one_social: Option<bool>,
-    #[serde(rename = "buysMax")]
+    #[serde(rename = "buysMax", skip_serializing_if = "Option::is_none")]
     pub buys_max: Option<i32>,
-    #[serde(rename = "buysMin")]
+    #[serde(rename = "buysMin", skip_serializing_if = "Option::is_none")]
     pub buys_min: Option<i32>,
-    #[serde(rename = "chainIds")]
-    pub chain_ids: Option<Vec<i32>>,
-    #[serde(rename = "freezeAuthDisabled")]
+    #[serde(rename = "chainIds", skip_serializing_if = "Option::is_none", with = "chain_id_list", default)]
+    pub chain_ids: Option<Vec<ChainId>>,
+    #[serde(rename = "freezeAuthDisabled", skip_serializing_if = "Option::is_none")]
     pub freeze_auth_disabled: Option<bool>,
-    #[serde(rename = "initialLiquidityMax")]
+    #[serde(rename = "initialLiquidityMax", skip_serializing_if = "Option::is_none")]
     pub initial_liquidity_max: Option<f64>,
-    #[serde(rename = "initialLiquidityMin")]
+    #[serde(rename = "initialLiquidityMin", skip_serializing_if = "Option::is_none")]
     pub initial_liquidity_min: Option<f64>,
-    pub interval: Option<String>,
-    #[serde(rename = "liquidityMax")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interval: Option<Interval>,
+    #[serde(rename = "liquidityMax", skip_serializing_if = "Option::is_none")]
     pub liquidity_max: Option<f64>,
-    #[serde(rename = "liquidityMin")]
+    #[serde(rename = "liquidityMin", skip_serializing_if = "Option::is_none")]
     pub liquidity_min: Option<f64>,
-    #[serde(rename = "lpBurned")]
+    #[serde(rename = "lpBurned", skip_serializing_if = "Option::is_none")]
     pub lp_burned: Option<bool>,
-    #[serde(rename = "marketCapMax")]
+    #[serde(rename = "marketCapMax", skip_serializing_if = "Option::is_none")]
     pub market_cap_max: Option<f64>,
-    #[serde(rename = "marketCapMin")]
+    #[serde(rename = "marketCapMin", skip_serializing_if = "Option::is_none")]
     pub market_cap_min: Option<f64>,
-    #[serde(rename = "mintAuthDisabled")]
+    #[serde(rename = "mintAuthDisabled", skip_serializing_if = "Option::is_none")]
     pub mint_auth_disabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub page: Option<i32>,
-    #[serde(rename = "sellsMax")]
+    #[serde(rename = "sellsMax", skip_serializing_if = "Option::is_none")]
     pub sells_max: Option<i32>,
-    #[serde(rename = "sellsMin")]
+    #[serde(rename = "sellsMin", skip_serializing_if = "Option::is_none")]
     pub sells_min: Option<i32>,
-    pub sorting: Option<String>,
-    #[serde(rename = "swapsMax")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sorting: Option<PairSorting>,
+    #[serde(rename = "swapsMax", skip_serializing_if = "Option::is_none")]
     pub swaps_max: Option<i32>,
-    #[serde(rename = "swapsMin")]
+    #[serde(rename = "swapsMin", skip_serializing_if = "Option::is_none")]
     pub swaps_min: Option<i32>,
-    #[serde(rename = "tokenTypes")]
+    #[serde(rename = "tokenTypes", skip_serializing_if = "Option::is_none", with = "string_list", default)]
     pub token_types: Option<Vec<String>>,
-    #[serde(rename = "top10Holders")]
+    #[serde(rename = "top10Holders", skip_serializing_if = "Option::is_none")]
     pub top10_holders: Option<bool>,
-    #[serde(rename = "volumeMax")]
+    #[serde(rename = "volumeMax", skip_serializing_if = "Option::is_none")]
     pub volume_max: Option<f64>,
-    #[serde(rename = "volumeMin")]
+    #[serde(rename = "volumeMin", skip_serializing_if = "Option::is_none")]
     pub volume_min: Option<f64>,
 }
 
-impl IntoIterator for TokenPairsParams {
-    type Item = (&'static str, String);
-    type IntoIter = vec::IntoIter<Self::Item>;
-
-    fn into_iter(self) -> Self::IntoIter {
-        let mut params = Vec::new();
+impl TokenPairsParams {
+    pub fn builder() -> TokenPairsParamsBuilder {
+        TokenPairsParamsBuilder::default()
+    }
 
-        if let Some(v) = self.at_least_one_social {
-            params.push(("atLeastOneSocial", v.to_string()));
-        }
-        if let Some(v) = self.buys_max {
-            params.push(("buysMax", v.to_string()));
-        }
-        if let Some(v) = self.buys_min {
-            params.push(("buysMin", v.to_string()));
-        }
-        if let Some(v) = self.chain_ids {
-            params.push(("chainIds", v.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(",")));
-        }
-        if let Some(v) = self.freeze_auth_disabled {
-            params.push(("freezeAuthDisabled", v.to_string()));
-        }
-        if let Some(v) = self.initial_liquidity_max {
-            params.push(("initialLiquidityMax", v.to_string()));
-        }
-        if let Some(v) = self.initial_liquidity_min {
-            params.push(("initialLiquidityMin", v.to_string()));
-        }
-        if let Some(v) = self.interval {
-            params.push(("interval", v));
+    /// Checks that every min/max pair is ordered, that amounts are not
+    /// negative and that the page is at least 1.
+    pub fn validate(&self) -> Result<(), ParamsError> {
+        check_range("buys", self.buys_min.map(f64::from), self.buys_max.map(f64::from))?;
+        check_range("sells", self.sells_min.map(f64::from), self.sells_max.map(f64::from))?;
+        check_range("swaps", self.swaps_min.map(f64::from), self.swaps_max.map(f64::from))?;
+        check_range("initialLiquidity", self.initial_liquidity_min, self.initial_liquidity_max)?;
+        check_range("liquidity", self.liquidity_min, self.liquidity_max)?;
+        check_range("marketCap", self.market_cap_min, self.market_cap_max)?;
+        check_range("volume", self.volume_min, self.volume_max)?;
+        match self.page {
+            Some(page) if page < 1 => Err(ParamsError::InvalidPage(page)),
+            _ => Ok(()),
         }
-        if let Some(v) = self.liquidity_max {
-            params.push(("liquidityMax", v.to_string()));
-        }
-        if let Some(v) = self.liquidity_min {
-            params.push(("liquidityMin", v.to_string()));
-        }
-        if let Some(v) = self.lp_burned {
-            params.push(("lpBurned", v.to_string()));
-        }
-        if let Some(v) = self.market_cap_max {
-            params.push(("marketCapMax", v.to_string()));
-        }
-        if let Some(v) = self.market_cap_min {
-            params.push(("marketCapMin", v.to_string()));
-        }
-        if let Some(v) = self.mint_auth_disabled {
-            params.push(("mintAuthDisabled", v.to_string()));
-        }
-        if let Some(v) = self.page {
-            params.push(("page", v.to_string()));
+    }
+}
+
+fn check_range(field: &'static str, min: Option<f64>, max: Option<f64>) -> Result<(), ParamsError> {
+    for value in min.iter().chain(max.iter()) {
+        if value.is_nan() {
+            return Err(ParamsError::NotANumber { field });
         }
-        if let Some(v) = self.sells_max {
-            params.push(("sellsMax", v.to_string()));
+        if *value < 0.0 {
+            return Err(ParamsError::Negative { field, value: *value });
         }
-        if let Some(v) = self.sells_min {
-            params.push(("sellsMin", v.to_string()));
+    }
+    match (min, max) {
+        (Some(min), Some(max)) if min > max => Err(ParamsError::InvalidRange { field, min, max }),
+        _ => Ok(()),
+    }
+}
+
+/// Fluent builder for [`TokenPairsParams`].
+///
+/// Range setters accept any Rust range, e.g. `.liquidity(1_000.0..50_000.0)`
+/// or `.buys(10..)`. The API treats bounds as inclusive, so an excluded
+/// integer bound is moved inwards by one while an excluded float bound is
+/// sent as is.
+#[derive(Debug, Clone, Default)]
+pub struct TokenPairsParamsBuilder {
+    params: TokenPairsParams,
+}
+
+impl TokenPairsParamsBuilder {
+    pub fn chain_ids<I: IntoIterator<Item = ChainId>>(mut self, chain_ids: I) -> Self {
+        self.params.chain_ids = Some(chain_ids.into_iter().collect());
+        self
+    }
+
+    pub fn chain(mut self, chain_id: ChainId) -> Self {
+        self.params.chain_ids.get_or_insert_with(Vec::new).push(chain_id);
+        self
+    }
+
+    pub fn token_types<I, S>(mut self, token_types: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.params.token_types = Some(token_types.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn interval(mut self, interval: Interval) -> Self {
+        self.params.interval = Some(interval);
+        self
+    }
+
+    pub fn sorting(mut self, sorting: PairSorting) -> Self {
+        self.params.sorting = Some(sorting);
+        self
+    }
+
+    pub fn page(mut self, page: i32) -> Self {
+        self.params.page = Some(page);
+        self
+    }
+
+    pub fn at_least_one_social(mut self, value: bool) -> Self {
+        self.params.at_least_one_social = Some(value);
+        self
+    }
+
+    pub fn freeze_auth_disabled(mut self, value: bool) -> Self {
+        self.params.freeze_auth_disabled = Some(value);
+        self
+    }
+
+    pub fn mint_auth_disabled(mut self, value: bool) -> Self {
+        self.params.mint_auth_disabled = Some(value);
+        self
+    }
+
+    pub fn lp_burned(mut self, value: bool) -> Self {
+        self.params.lp_burned = Some(value);
+        self
+    }
+
+    pub fn top10_holders(mut self, value: bool) -> Self {
+        self.params.top10_holders = Some(value);
+        self
+    }
+
+    pub fn buys<R: RangeBounds<i32>>(mut self, range: R) -> Self {
+        (self.params.buys_min, self.params.buys_max) = int_bounds(range);
+        self
+    }
+
+    pub fn sells<R: RangeBounds<i32>>(mut self, range: R) -> Self {
+        (self.params.sells_min, self.params.sells_max) = int_bounds(range);
+        self
+    }
+
+    pub fn swaps<R: RangeBounds<i32>>(mut self, range: R) -> Self {
+        (self.params.swaps_min, self.params.swaps_max) = int_bounds(range);
+        self
+    }
+
+    pub fn initial_liquidity<R: RangeBounds<f64>>(mut self, range: R) -> Self {
+        (self.params.initial_liquidity_min, self.params.initial_liquidity_max) = float_bounds(range);
+        self
+    }
+
+    pub fn liquidity<R: RangeBounds<f64>>(mut self, range: R) -> Self {
+        (self.params.liquidity_min, self.params.liquidity_max) = float_bounds(range);
+        self
+    }
+
+    pub fn market_cap<R: RangeBounds<f64>>(mut self, range: R) -> Self {
+        (self.params.market_cap_min, self.params.market_cap_max) = float_bounds(range);
+        self
+    }
+
+    pub fn volume<R: RangeBounds<f64>>(mut self, range: R) -> Self {
+        (self.params.volume_min, self.params.volume_max) = float_bounds(range);
+        self
+    }
+
+    pub fn build(self) -> Result<TokenPairsParams, ParamsError> {
+        self.params.validate()?;
+        Ok(self.params)
+    }
+}
+
+fn int_bounds<R: RangeBounds<i32>>(range: R) -> (Option<i32>, Option<i32>) {
+    let min = match range.start_bound() {
+        Bound::Included(v) => Some(*v),
+        Bound::Excluded(v) => Some(v.saturating_add(1)),
+        Bound::Unbounded => None,
+    };
+    let max = match range.end_bound() {
+        Bound::Included(v) => Some(*v),
+        Bound::Excluded(v) => Some(v.saturating_sub(1)),
+        Bound::Unbounded => None,
+    };
+    (min, max)
+}
+
+fn float_bounds<R: RangeBounds<f64>>(range: R) -> (Option<f64>, Option<f64>) {
+    let bound = |bound: Bound<&f64>| match bound {
+        Bound::Included(v) | Bound::Excluded(v) => Some(*v),
+        Bound::Unbounded => None,
+    };
+    (bound(range.start_bound()), bound(range.end_bound()))
+}
+
+/// The API expects list filters as a single comma separated value; a JSON
+/// list is accepted too when deserializing. Implemented by hand because
+/// `#[serde(untagged)]` cannot buffer numbers once serde_json's
+/// `arbitrary_precision` feature is on, which the `decimal` feature enables.
+enum CommaList<T> {
+    Joined(String),
+    Items(Vec<T>),
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for CommaList<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct CommaListVisitor<T>(PhantomData<T>);
+
+        impl<'de, T: Deserialize<'de>> Visitor<'de> for CommaListVisitor<T> {
+            type Value = CommaList<T>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a comma separated string or a list")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                Ok(CommaList::Joined(value.to_string()))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut items = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(item) = seq.next_element()? {
+                    items.push(item);
+                }
+                Ok(CommaList::Items(items))
+            }
         }
-        if let Some(v) = self.sorting {
-            params.push(("sorting", v));
+
+        deserializer.deserialize_any(CommaListVisitor(PhantomData))
+    }
+}
+
+mod chain_id_list {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &Option<Vec<ChainId>>, serializer: S) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(ids) => {
+                let joined = ids.iter().map(|id| id.id().to_string()).collect::<Vec<_>>().join(",");
+                serializer.serialize_str(&joined)
+            }
+            None => serializer.serialize_none(),
         }
-        if let Some(v) = self.swaps_max {
-            params.push(("swapsMax", v.to_string()));
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Vec<ChainId>>, D::Error> {
+        match Option::<CommaList<ChainId>>::deserialize(deserializer)? {
+            Some(CommaList::Joined(joined)) => joined
+                .split(',')
+                .filter(|id| !id.trim().is_empty())
+                .map(|id| id.parse().map_err(serde::de::Error::custom))
+                .collect::<Result<Vec<_>, _>>()
+                .map(Some),
+            Some(CommaList::Items(ids)) => Ok(Some(ids)),
+            None => Ok(None),
         }
-        if let Some(v) = self.swaps_min {
-            params.push(("swapsMin", v.to_string()));
+    }
+}
+
+mod string_list {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &Option<Vec<String>>, serializer: S) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(items) => serializer.serialize_str(&items.join(",")),
+            None => serializer.serialize_none(),
         }
-        if let Some(v) = self.token_types {
-            params.push(("tokenTypes", v.join(",")));
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Vec<String>>, D::Error> {
+        Ok(match Option::<CommaList<String>>::deserialize(deserializer)? {
+            Some(CommaList::Joined(joined)) => Some(
+                joined
+                    .split(',')
+                    .filter(|item| !item.trim().is_empty())
+                    .map(|item| item.trim().to_string())
+                    .collect(),
+            ),
+            Some(CommaList::Items(items)) => Some(items),
+            None => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_chain_id_from_i32() {
+        assert_eq!(ChainId::from(900), ChainId::Solana);
+        assert_eq!(ChainId::from(8453), ChainId::Base);
+        assert!(matches!(ChainId::from(7), ChainId::Unknown(7)));
+        assert_eq!(ChainId::Unknown(1000), ChainId::Tron);
+        assert_eq!(i32::from(ChainId::Blast), 81457);
+    }
+
+    #[test]
+    fn test_chain_id_display_and_from_str() {
+        assert_eq!(ChainId::Solana.to_string(), "solana");
+        assert_eq!(ChainId::Unknown(7).to_string(), "7");
+        assert_eq!("base".parse::<ChainId>().unwrap(), ChainId::Base);
+        assert_eq!("BSC".parse::<ChainId>().unwrap(), ChainId::Bsc);
+        assert_eq!("42161".parse::<ChainId>().unwrap(), ChainId::Arbitrum);
+        assert_eq!("7".parse::<ChainId>().unwrap(), ChainId::Unknown(7));
+        assert!("dogechain".parse::<ChainId>().is_err());
+    }
+
+    #[test]
+    fn test_chain_id_serde() {
+        assert_eq!(serde_json::to_string(&ChainId::Solana).unwrap(), "900");
+        assert_eq!(serde_json::from_str::<ChainId>("1").unwrap(), ChainId::Ethereum);
+        assert_eq!(serde_json::from_str::<ChainId>("12345").unwrap(), ChainId::Unknown(12345));
+
+        let mut chains = HashMap::new();
+        chains.insert(ChainId::Unknown(900), "solana");
+        assert_eq!(chains.get(&ChainId::Solana), Some(&"solana"));
+    }
+
+    #[test]
+    fn test_interval_round_trip() {
+        assert_eq!(Interval::OneDay.to_string(), "1d");
+        assert_eq!("1d".parse::<Interval>().unwrap(), Interval::OneDay);
+        assert_eq!("1D".parse::<Interval>().unwrap(), Interval::Other("1D".to_string()));
+        assert_eq!("24h".parse::<Interval>().unwrap(), Interval::Other("24h".to_string()));
+        assert_eq!(serde_json::to_string(&Interval::OneDay).unwrap(), r#""1d""#);
+        assert_eq!(serde_json::from_str::<Interval>(r#""1d""#).unwrap(), Interval::OneDay);
+    }
+
+    #[test]
+    fn test_pair_sorting_round_trip() {
+        assert_eq!(PairSorting::from("volume").to_string(), "volume");
+        assert_eq!("hot".parse::<PairSorting>().unwrap(), PairSorting::Other("hot".to_string()));
+        assert_eq!(serde_json::to_string(&PairSorting::from("volume")).unwrap(), r#""volume""#);
+        assert_eq!(serde_json::from_str::<PairSorting>(r#""volume""#).unwrap(), PairSorting::from("volume"));
+    }
+
+    #[test]
+    fn test_token_pairs_params_serialization() {
+        let params = TokenPairsParams {
+            chain_ids: Some(vec![ChainId::Solana, ChainId::Base]),
+            interval: Some(Interval::OneDay),
+            sorting: Some(PairSorting::from("volume")),
+            token_types: Some(vec!["pumpfun".to_string(), "raydium".to_string()]),
+            liquidity_min: Some(1000.0),
+            ..Default::default()
+        };
+        let value = serde_json::to_value(&params).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "chainIds": "900,8453",
+                "interval": "1d",
+                "sorting": "volume",
+                "tokenTypes": "pumpfun,raydium",
+                "liquidityMin": 1000.0
+            })
+        );
+
+        let parsed: TokenPairsParams = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed.chain_ids, Some(vec![ChainId::Solana, ChainId::Base]));
+        assert_eq!(parsed.token_types, Some(vec!["pumpfun".to_string(), "raydium".to_string()]));
+
+        let parsed: TokenPairsParams = serde_json::from_str(r#"{"chainIds": [1, 56], "tokenTypes": ["meme"]}"#).unwrap();
+        assert_eq!(parsed.chain_ids, Some(vec![ChainId::Ethereum, ChainId::Bsc]));
+        assert_eq!(parsed.token_types, Some(vec!["meme".to_string()]));
+    }
+
+    #[test]
+    fn test_token_pairs_params_builder_ranges() {
+        let params = TokenPairsParams::builder()
+            .chain(ChainId::Solana)
+            .liquidity(1_000.0..50_000.0)
+            .buys(10..)
+            .sells(..=5)
+            .swaps(1..4)
+            .interval(Interval::OneDay)
+            .build()
+            .unwrap();
+
+        assert_eq!(params.chain_ids, Some(vec![ChainId::Solana]));
+        assert_eq!((params.liquidity_min, params.liquidity_max), (Some(1_000.0), Some(50_000.0)));
+        assert_eq!((params.buys_min, params.buys_max), (Some(10), None));
+        assert_eq!((params.sells_min, params.sells_max), (None, Some(5)));
+        assert_eq!((params.swaps_min, params.swaps_max), (Some(1), Some(3)));
+        assert_eq!(params.interval, Some(Interval::OneDay));
+    }
+
+    #[test]
+    fn test_token_pairs_params_validation() {
+        let err = TokenPairsParams::builder().liquidity(50_000.0..=1_000.0).build().unwrap_err();
+        assert_eq!(err, ParamsError::InvalidRange { field: "liquidity", min: 50_000.0, max: 1_000.0 });
+
+        let err = TokenPairsParams::builder().market_cap(-5.0..).build().unwrap_err();
+        assert_eq!(err, ParamsError::Negative { field: "marketCap", value: -5.0 });
+
+        let err = TokenPairsParams::builder().volume(..f64::NAN).build().unwrap_err();
+        assert_eq!(err, ParamsError::NotANumber { field: "volume" });
+
+        let err = TokenPairsParams::builder().liquidity(f64::NAN..).build().unwrap_err();
+        assert_eq!(err, ParamsError::NotANumber { field: "liquidity" });
+
+        let err = TokenPairsParams::builder().page(0).build().unwrap_err();
+        assert_eq!(err, ParamsError::InvalidPage(0));
+
+        let params = TokenPairsParams {
+            buys_min: Some(10),
+            buys_max: Some(2),
+            ..Default::default()
+        };
+        assert!(params.validate().is_err());
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_decimal_numbers_are_exact() {
+        let json = r#"{
+            "marketCapUsd": 123456789012345678.123456789,
+            "timestamp": 1609459200,
+            "tokenLiquidityUsd": 0.000000001234567890123
+        }"#;
+        let ath: TokenATH = serde_json::from_str(json).unwrap();
+        assert_eq!(ath.market_cap_usd.to_string(), "123456789012345678.123456789");
+        assert_eq!(ath.token_liquidity_usd.to_string(), "0.000000001234567890123");
+        assert_eq!(number_to_f64(ath.token_liquidity_usd), 0.000000001234567890123);
+    }
+
+    const SECONDS: i64 = 1_700_000_000;
+    const MILLIS: i64 = 1_700_000_000_123;
+
+    /// Each raw timestamp and the Unix milliseconds it must be read as.
+    const TIMESTAMP_CASES: [(i64, i64); 2] = [(SECONDS, SECONDS * 1000), (MILLIS, MILLIS)];
+
+    fn with_timestamp<T: serde::de::DeserializeOwned>(mut value: serde_json::Value, pointer: &str, raw: i64) -> T {
+        *value.pointer_mut(pointer).unwrap() = raw.into();
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn token_pair_value() -> serde_json::Value {
+        serde_json::json!({
+            "buyTxnCount": 1, "chainId": 900, "contractCreator": "c", "createdTimestamp": 0,
+            "initialAssetLiquidity": 1.0, "initialUsdLiquidity": 1.0, "lpBurned": true, "lpCreator": "lp",
+            "marketId": "m", "name": "n", "pooledAsset": 1.0, "pooledToken": 1.0, "priceChangePercent": 0.0,
+            "sellTxnCount": 1, "symbol": "S", "tokenLiquidityAsset": 1.0, "tokenLiquidityUsd": 1.0,
+            "tokenMarketCapAsset": 1.0, "tokenMarketCapUsd": 1.0, "tokenMint": "t", "tokenPriceAsset": 1.0,
+            "tokenPriceUsd": 1.0, "tokenType": "meme", "top10HoldingPercent": 0.0, "totalSupply": 1.0,
+            "transactionCount": 2, "volumeAsset": 1.0, "volumeUsd": 1.0,
+            "migrationState": {"durationMinutes": 1, "makers": 1, "migrationTimestamp": 0, "volume": 1.0}
+        })
+    }
+
+    #[test]
+    fn test_unix_millis() {
+        assert_eq!(unix_millis(SECONDS), SECONDS * 1000);
+        assert_eq!(unix_millis(MILLIS), MILLIS);
+        assert_eq!(unix_millis(999_999_999_999), 999_999_999_999_000);
+        assert_eq!(unix_millis(1_000_000_000_000), 1_000_000_000_000);
+        assert_eq!(unix_millis(0), 0);
+    }
+
+    #[test]
+    fn test_token_pair_timestamps() {
+        for (raw, expected) in TIMESTAMP_CASES {
+            let pair: TokenPair = with_timestamp(token_pair_value(), "/createdTimestamp", raw);
+            assert_eq!(timestamp_millis(pair.created_timestamp), expected);
+
+            let pair: TokenPair = with_timestamp(token_pair_value(), "/migrationState/migrationTimestamp", raw);
+            assert_eq!(timestamp_millis(pair.migration_state.unwrap().migration_timestamp), expected);
         }
-        if let Some(v) = self.top10_holders {
-            params.push(("top10Holders", v.to_string()));
+    }
+
+    #[test]
+    fn test_token_ath_timestamp() {
+        let value = serde_json::json!({"marketCapUsd": 1.0, "timestamp": 0, "tokenLiquidityUsd": 1.0});
+        for (raw, expected) in TIMESTAMP_CASES {
+            let ath: TokenATH = with_timestamp(value.clone(), "/timestamp", raw);
+            assert_eq!(timestamp_millis(ath.timestamp), expected);
         }
-        if let Some(v) = self.volume_max {
-            params.push(("volumeMax", v.to_string()));
+    }
+
+    #[test]
+    fn test_wallet_pnl_holder_since() {
+        let value = serde_json::json!({
+            "holderSince": 0, "investedAmount": 1.0, "investedTxns": 1, "pnlPercent": 0.0, "pnlUsd": 0.0,
+            "remainingTokens": 0.0, "remainingUsd": 0.0, "soldAmount": 0.0, "soldTxns": 0
+        });
+        for (raw, expected) in TIMESTAMP_CASES {
+            let pnl: WalletPnL = with_timestamp(value.clone(), "/holderSince", raw);
+            assert_eq!(timestamp_millis(pnl.holder_since), expected);
         }
-        if let Some(v) = self.volume_min {
-            params.push(("volumeMin", v.to_string()));
+    }
+
+    #[test]
+    fn test_chain_action_timestamp() {
+        let value = serde_json::json!({
+            "signer": "s", "transactionId": "tx", "marketId": "m", "actionType": "buy", "tokenAmount": 1.0,
+            "assetAmount": 1.0, "tokenPriceUsd": 1.0, "tokenPriceAsset": 1.0, "tokenMarketCapAsset": 1.0,
+            "tokenMarketCapUsd": 1.0, "tokenLiquidityAsset": 1.0, "tokenLiquidityUsd": 1.0, "pooledToken": 1.0,
+            "pooledAsset": 1.0, "actionTimestamp": 0
+        });
+        for (raw, expected) in TIMESTAMP_CASES {
+            let action: ChainAction = with_timestamp(value.clone(), "/actionTimestamp", raw);
+            assert_eq!(timestamp_millis(action.action_timestamp), expected);
         }
+    }
 
-        params.into_iter()
-    }
-}
-
-impl Default for TokenPairsParams {
-    fn default() -> Self {
-        TokenPairsParams {
-            at_least_one_social: None,
-            buys_max: None,
-            buys_min: None,
-            chain_ids: None,
-            freeze_auth_disabled: None,
-            initial_liquidity_max: None,
-            initial_liquidity_min: None,
-            interval: None,
-            liquidity_max: None,
-            liquidity_min: None,
-            lp_burned: None,
-            market_cap_max: None,
-            market_cap_min: None,
-            mint_auth_disabled: None,
-            page: None,
-            sells_max: None,
-            sells_min: None,
-            sorting: None,
-            swaps_max: None,
-            swaps_min: None,
-            token_types: None,
-            top10_holders: None,
-            volume_max: None,
-            volume_min: None,
+    #[test]
+    fn test_token_search_result_timestamp() {
+        let value = serde_json::json!({
+            "chainId": 900, "marketId": "m", "createdTimestamp": 0, "name": "n", "symbol": "S", "tokenMint": "t",
+            "tokenType": "meme", "percentChange24h": 0.0, "pooledAsset": 1.0, "tokenLiquidityUsd": 1.0,
+            "tokenMarketCapUsd": 1.0, "tokenPriceUsd": 1.0, "volumeUsd": 1.0
+        });
+        for (raw, expected) in TIMESTAMP_CASES {
+            let result: TokenSearchResult = with_timestamp(value.clone(), "/createdTimestamp", raw);
+            assert_eq!(timestamp_millis(result.created_timestamp), expected);
         }
     }
-}
\ No newline at end of file
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_chrono_timestamps_serialize_as_millis() {
+        let ath: TokenATH = serde_json::from_value(
+            serde_json::json!({"marketCapUsd": 1.0, "timestamp": SECONDS, "tokenLiquidityUsd": 1.0}),
+        )
+        .unwrap();
+        assert_eq!(ath.timestamp.to_rfc3339(), "2023-11-14T22:13:20+00:00");
+        assert_eq!(serde_json::to_value(&ath).unwrap()["timestamp"], SECONDS * 1000);
+    }
+}
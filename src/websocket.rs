@@ -1,27 +1,33 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot, Mutex};
 use async_trait::async_trait;
 use serde::{Serialize, Deserialize};
 use serde_json::Value;
 use tokio_tungstenite::{connect_async, tungstenite::protocol::Message, WebSocketStream, MaybeTlsStream};
+use futures_util::stream::{self, BoxStream};
 use futures_util::{SinkExt, StreamExt};
 use tokio::net::TcpStream;
+use tokio::time::Instant;
 use crate::types::{ChainAction, TokenPair};
-use crate::errors::VyperError;  
+use crate::errors::VyperError;
+use crate::retry::exponential_backoff;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum FeedType {
     TokenEvents,
     MigrationEvents,
     WalletEvents,
 }
 
-impl ToString for FeedType {
-    fn to_string(&self) -> String {
+impl fmt::Display for FeedType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            FeedType::TokenEvents => "token-events".to_string(),
-            FeedType::MigrationEvents => "migration-events".to_string(),
-            FeedType::WalletEvents => "wallet-events".to_string(),
+            FeedType::TokenEvents => write!(f, "token-events"),
+            FeedType::MigrationEvents => write!(f, "migration-events"),
+            FeedType::WalletEvents => write!(f, "wallet-events"),
         }
     }
 }
@@ -33,7 +39,7 @@ pub enum SubscriptionMessageType {
     Unsubscribe,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub enum SubscriptionType {
     PumpfunTokens,
@@ -54,7 +60,248 @@ pub struct WalletSubscriptionMessage {
     pub wallets: Vec<String>,
 }
 
+/// Subscriptions currently active on the feed, kept so they can be replayed
+/// after a reconnect.
+#[derive(Debug, Default)]
+struct Subscriptions {
+    token_types: Vec<SubscriptionType>,
+    wallets: Vec<String>,
+}
+
+/// Either kind of subscription message, as sent to the server.
+#[derive(Deserialize)]
+struct SentSubscription {
+    action: SubscriptionMessageType,
+    #[serde(default)]
+    types: Vec<SubscriptionType>,
+    #[serde(default)]
+    wallets: Vec<String>,
+}
+
+impl Subscriptions {
+    /// Updates the active set from a message that was sent successfully.
+    /// Messages that are not subscription messages are ignored.
+    fn record(&mut self, message: Value) {
+        let message: SentSubscription = match serde_json::from_value(message) {
+            Ok(message) => message,
+            Err(_) => return,
+        };
+        match message.action {
+            SubscriptionMessageType::Subscribe => {
+                for subscription_type in message.types {
+                    if !self.token_types.contains(&subscription_type) {
+                        self.token_types.push(subscription_type);
+                    }
+                }
+                for wallet in message.wallets {
+                    if !self.wallets.contains(&wallet) {
+                        self.wallets.push(wallet);
+                    }
+                }
+            }
+            SubscriptionMessageType::Unsubscribe => {
+                self.token_types.retain(|subscription_type| !message.types.contains(subscription_type));
+                self.wallets.retain(|wallet| !message.wallets.contains(wallet));
+            }
+        }
+    }
+
+    /// Messages that re-create every active subscription on a new connection.
+    fn replay_messages(&self) -> Result<Vec<String>, VyperError> {
+        let mut messages = Vec::new();
+        if !self.token_types.is_empty() {
+            messages.push(serde_json::to_string(&TokenSubscriptionMessage {
+                action: SubscriptionMessageType::Subscribe,
+                types: self.token_types.clone(),
+            })?);
+        }
+        if !self.wallets.is_empty() {
+            messages.push(serde_json::to_string(&WalletSubscriptionMessage {
+                action: SubscriptionMessageType::Subscribe,
+                wallets: self.wallets.clone(),
+            })?);
+        }
+        Ok(messages)
+    }
+}
+
+/// Controls how [`VyperWebsocketClient`] reconnects a feed after its
+/// connection drops.
+///
+/// Each reconnect attempt waits for an exponential backoff starting at
+/// `base_delay` and capped at `max_delay`, reconnects to the same
+/// [`FeedType`] and replays every active subscription.
+#[derive(Debug, Clone)]
+pub struct ReconnectPolicy {
+    /// Attempts per disconnect before giving up. `None` retries forever.
+    pub max_attempts: Option<u32>,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    /// Fraction of each backoff (0.0 - 1.0) that is randomised.
+    pub jitter: f64,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: None,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: 0.2,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// Returns how long to wait before reconnect `attempt` (1-based), or
+    /// `None` once the attempts are used up.
+    pub fn next_delay(&self, attempt: u32) -> Option<Duration> {
+        match self.max_attempts {
+            Some(max_attempts) if attempt > max_attempts => None,
+            _ => Some(exponential_backoff(self.base_delay, self.max_delay, self.jitter, attempt)),
+        }
+    }
+}
+
+/// Connection lifecycle events, delivered to the handler set with
+/// [`VyperWebsocketClient::set_connection_handler`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConnectionEvent {
+    /// The connection dropped while listening, or stopped answering pings.
+    Disconnected { feed_type: FeedType, reason: String },
+    /// Reconnect `attempt` starts after `delay`.
+    Reconnecting { feed_type: FeedType, attempt: u32, delay: Duration },
+    /// The connection is back and the subscriptions have been replayed.
+    Reconnected { feed_type: FeedType, attempt: u32 },
+    /// Every attempt failed; `listen` returns the last error, even if other
+    /// feeds are still connected.
+    ReconnectFailed { feed_type: FeedType, attempts: u32 },
+}
+
+/// Sends WebSocket Ping frames on every connection and treats a connection
+/// as dead when nothing comes back in time.
+///
+/// Any frame received from the server counts as a reply, so a busy feed is
+/// never dropped for a late pong. The heartbeat pauses while the consumer is
+/// behind and the connection is not being read, and restarts with a full
+/// interval once reading resumes.
+#[derive(Debug, Clone)]
+pub struct HeartbeatConfig {
+    /// Time between pings.
+    pub interval: Duration,
+    /// How long to wait for a reply to a ping before closing the connection.
+    pub pong_timeout: Duration,
+}
+
+impl Default for HeartbeatConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(30),
+            pong_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Heartbeat state of one connection.
+struct Heartbeat {
+    config: HeartbeatConfig,
+    next_ping: Instant,
+    /// Set while a ping is unanswered.
+    pong_deadline: Option<Instant>,
+}
+
+impl Heartbeat {
+    fn new(config: HeartbeatConfig) -> Self {
+        Self {
+            next_ping: Instant::now() + config.interval,
+            pong_deadline: None,
+            config,
+        }
+    }
+
+    fn due_at(&self) -> Instant {
+        match self.pong_deadline {
+            Some(deadline) => deadline.min(self.next_ping),
+            None => self.next_ping,
+        }
+    }
+
+    fn alive(&mut self) {
+        self.pong_deadline = None;
+    }
+
+    fn expired(&self, now: Instant) -> bool {
+        matches!(self.pong_deadline, Some(deadline) if now >= deadline)
+    }
+
+    /// Restarts the schedule after reads were paused; a pong that could not
+    /// be read in the meantime is not held against the connection.
+    fn resume(&mut self, now: Instant) {
+        self.pong_deadline = None;
+        self.next_ping = now + self.config.interval;
+    }
+
+    fn ping_sent(&mut self, now: Instant) {
+        self.next_ping = now + self.config.interval;
+        if self.pong_deadline.is_none() {
+            self.pong_deadline = Some(now + self.config.pong_timeout);
+        }
+    }
+}
+
+/// Completes when `heartbeat` needs attention, or never without one.
+async fn heartbeat_due(heartbeat: Option<&Heartbeat>) {
+    match heartbeat {
+        Some(heartbeat) => tokio::time::sleep_until(heartbeat.due_at()).await,
+        None => std::future::pending().await,
+    }
+}
+
+/// A frame received from the server.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Frame {
+    Text(String),
+    Ping(Vec<u8>),
+    Pong(Vec<u8>),
+}
+
+/// An item of [`VyperWebsocketClient::events`].
+#[derive(Debug)]
+pub enum WsEvent {
+    /// A message from the [`FeedType::TokenEvents`] feed.
+    TokenEvent(TokenPair),
+    /// A message from the [`FeedType::MigrationEvents`] feed.
+    MigrationEvent(TokenPair),
+    /// A message from the [`FeedType::WalletEvents`] feed.
+    WalletEvent(ChainAction),
+    /// A message that is not an event of the connected feed, such as a
+    /// subscription acknowledgement or an error reported by the server.
+    Control(Value),
+    /// A change in the state of the connection.
+    Connection(ConnectionEvent),
+}
+
+/// An item of [`VyperWebsocketClient::feed_events`].
+#[derive(Debug)]
+pub struct FeedEvent {
+    /// The feed whose connection produced `event`.
+    pub feed_type: FeedType,
+    pub event: WsEvent,
+}
+
+/// Parses a text message from `feed_type` into an event.
+fn parse_event(text: &str, feed_type: &FeedType) -> Result<WsEvent, VyperError> {
+    let value: Value = serde_json::from_str(text)?;
+    let event = match feed_type {
+        FeedType::TokenEvents => TokenPair::deserialize(&value).map(WsEvent::TokenEvent),
+        FeedType::MigrationEvents => TokenPair::deserialize(&value).map(WsEvent::MigrationEvent),
+        FeedType::WalletEvents => ChainAction::deserialize(&value).map(WsEvent::WalletEvent),
+    };
+    Ok(event.unwrap_or(WsEvent::Control(value)))
+}
+
 type MessageHandler = Arc<Mutex<dyn FnMut(Value) + Send + Sync>>;
+type ConnectionHandler = Arc<Mutex<dyn FnMut(ConnectionEvent) + Send + Sync>>;
 
 #[async_trait]
 pub trait WebSocketConnection: Send + Sync {
@@ -62,14 +309,49 @@ pub trait WebSocketConnection: Send + Sync {
     async fn send(&mut self, data: &str) -> Result<(), VyperError>;
     async fn receive(&mut self) -> Result<String, VyperError>;
     async fn close(&mut self) -> Result<(), VyperError>;
+
+    /// Receives the next text or control frame. Connections that do not
+    /// surface control frames only need to implement
+    /// [`receive`](WebSocketConnection::receive).
+    ///
+    /// The default never yields [`Frame::Pong`], so with a [`HeartbeatConfig`]
+    /// enabled a feed that stays quiet for longer than the pong timeout is
+    /// treated as dead and closed.
+    async fn receive_frame(&mut self) -> Result<Frame, VyperError> {
+        self.receive().await.map(Frame::Text)
+    }
+
+    /// Sends a Ping frame. The default reports that pings are unsupported,
+    /// which makes the heartbeat drop the connection; implement it to use
+    /// [`VyperWebsocketClient::with_heartbeat`] or
+    /// [`VyperWebsocketClient::ping`].
+    async fn send_ping(&mut self, _payload: &[u8]) -> Result<(), VyperError> {
+        Err(control_frames_unsupported())
+    }
+
+    /// Answers a Ping frame surfaced by
+    /// [`receive_frame`](WebSocketConnection::receive_frame). The default
+    /// reports that pongs are unsupported.
+    async fn send_pong(&mut self, _payload: &[u8]) -> Result<(), VyperError> {
+        Err(control_frames_unsupported())
+    }
 }
 
 pub struct VyperWebsocketClient {
     base_url: String,
     api_key: String,
-    conn: Arc<Mutex<Option<Box<dyn WebSocketConnection>>>>,
+    /// Connections that are not driven yet, by feed. [`connect`] reuses
+    /// one supplied here; otherwise it is handed to a background task on
+    /// first use.
+    ///
+    /// [`connect`]: VyperWebsocketClient::connect
+    conn: Arc<Mutex<HashMap<FeedType, Box<dyn WebSocketConnection>>>>,
+    drivers: Arc<Mutex<HashMap<FeedType, DriverHandle>>>,
+    events: Arc<Mutex<EventChannel>>,
     message_handler: Option<MessageHandler>,
-    current_feed_type: Arc<Mutex<Option<FeedType>>>,
+    connection_handler: Option<ConnectionHandler>,
+    reconnect_policy: Option<ReconnectPolicy>,
+    heartbeat: Option<HeartbeatConfig>,
 }
 
 impl VyperWebsocketClient {
@@ -77,125 +359,252 @@ impl VyperWebsocketClient {
         Self {
             base_url: "wss://api.vyper.trade/api/v1/ws".to_string(),
             api_key,
-            conn: Arc::new(Mutex::new(None)),
+            conn: Arc::new(Mutex::new(HashMap::new())),
+            drivers: Arc::new(Mutex::new(HashMap::new())),
+            events: Arc::new(Mutex::new(EventChannel::closed())),
             message_handler: None,
-            current_feed_type: Arc::new(Mutex::new(None)),
+            connection_handler: None,
+            reconnect_policy: None,
+            heartbeat: None,
         }
     }
 
-    pub async fn connect(&self, feed_type: FeedType) -> Result<(), VyperError> {
-        let mut conn_guard = self.conn.lock().await;
-        let url = format!("{}/{}?apiKey={}", self.base_url, feed_type.to_string(), self.api_key);
-
-        if conn_guard.is_none() {
-            let mut new_conn: Box<dyn WebSocketConnection> = Box::new(WebSocketImpl::new());
-            new_conn.connect(&url).await.map_err(|e| VyperError::WebsocketError {
-                message: e.to_string(),
-                status_code: None,
-                connection_info: Some(url.clone()),
-            })?;
-            *conn_guard = Some(new_conn);
-        } else {
-            conn_guard.as_mut().unwrap().connect(&url).await.map_err(|e| VyperError::WebsocketError {
-                message: e.to_string(),
-                status_code: None,
-                connection_info: Some(url.clone()),
-            })?;
-        }
+    /// Reconnects a feed when its connection drops. Without a policy the
+    /// feed is closed on the first receive error, and
+    /// [`listen`](VyperWebsocketClient::listen) returns `Ok(())` once no
+    /// feed is left.
+    pub fn with_reconnect_policy(mut self, policy: ReconnectPolicy) -> Self {
+        self.reconnect_policy = Some(policy);
+        self
+    }
 
-        let mut feed_type_guard = self.current_feed_type.lock().await;
-        *feed_type_guard = Some(feed_type);
+    /// Pings the server while connected and drops the connection when it
+    /// stops answering. Combine with a [`ReconnectPolicy`] to reconnect
+    /// automatically.
+    pub fn with_heartbeat(mut self, heartbeat: HeartbeatConfig) -> Self {
+        self.heartbeat = Some(heartbeat);
+        self
+    }
 
-        Ok(())
+    fn feed_url(&self, feed_type: &FeedType) -> String {
+        format!("{}/{}?apiKey={}", self.base_url, feed_type, self.api_key)
     }
 
-    pub async fn subscribe<M: Serialize>(&self, feed_type: FeedType, message: M) -> Result<(), VyperError> {
-        let mut conn_guard = self.conn.lock().await;
-        let feed_type_guard = self.current_feed_type.lock().await;
+    /// Connects to `feed_type`. Connections to other feeds stay open and
+    /// their events are merged into the same stream, including one that is
+    /// already being consumed; an existing connection to the same feed is
+    /// closed first.
+    pub async fn connect(&self, feed_type: FeedType) -> Result<(), VyperError> {
+        // Keep the event channel open while the old connection is replaced,
+        // so a running consumer does not see the end of the stream.
+        let _events = self.events.lock().await.sender();
+        self.stop_driver(&feed_type).await?;
+        let url = self.feed_url(&feed_type);
+
+        let pending = self.conn.lock().await.remove(&feed_type);
+        let mut conn = pending.unwrap_or_else(|| Box::new(WebSocketImpl::new()));
+        conn.connect(&url).await.map_err(|e| VyperError::WebsocketError {
+            message: e.to_string(),
+            status_code: None,
+            connection_info: Some(url.clone()),
+        })?;
 
-        if conn_guard.is_none() {
-            return Err(VyperError::WebsocketError {
-                message: "Not connected".to_string(),
-                status_code: None,
-                connection_info: None,
-            });
-        }
+        let driver = self.spawn_driver(feed_type.clone(), conn).await;
+        self.drivers.lock().await.insert(feed_type, driver);
+        Ok(())
+    }
 
-        if feed_type != *feed_type_guard.as_ref().unwrap() {
-            return Err(VyperError::WebsocketError {
-                message: "Feed type mismatch".to_string(),
-                status_code: None,
-                connection_info: None,
-            });
-        }
+    /// Feeds with an open connection.
+    pub async fn connected_feeds(&self) -> Vec<FeedType> {
+        let drivers = self.drivers.lock().await;
+        let conn = self.conn.lock().await;
+        [FeedType::TokenEvents, FeedType::MigrationEvents, FeedType::WalletEvents]
+            .iter()
+            .filter(|feed_type| {
+                conn.contains_key(feed_type)
+                    || drivers.get(feed_type).is_some_and(|driver| !driver.commands.is_closed())
+            })
+            .cloned()
+            .collect()
+    }
 
-        let data = serde_json::to_string(&message).map_err(|e| VyperError::DeserializeError(e))?;
-        conn_guard.as_mut().unwrap().send(&data).await.map_err(|e| VyperError::WebsocketError {
+    /// Sends a subscription message on the connection to `feed_type`. This
+    /// can be called at any time, including while another task is listening.
+    pub async fn subscribe<M: Serialize>(&self, feed_type: FeedType, message: M) -> Result<(), VyperError> {
+        let message = serde_json::to_value(&message).map_err(VyperError::DeserializeError)?;
+        self.command(&feed_type, |reply| Command::Send { message, reply }).await.map_err(|e| VyperError::WebsocketError {
             message: e.to_string(),
             status_code: None,
             connection_info: None,
-        })?;
-
-        Ok(())
+        })
     }
 
     pub async fn unsubscribe<M: Serialize>(&self, feed_type: FeedType, message: M) -> Result<(), VyperError> {
         self.subscribe(feed_type, message).await
     }
 
+    /// Receives messages from every connected feed and passes them to the
+    /// message handler until the last connection drops. With a
+    /// [`ReconnectPolicy`] connections are re-established instead, and an
+    /// error is only returned once the policy gives up.
+    ///
+    /// `listen` returns on the first error from any feed. The remaining feeds
+    /// stay connected, but stop reading from their sockets, and pause their
+    /// heartbeats, once 256 events are waiting; call `listen` again to carry on with them, or
+    /// [`disconnect`](VyperWebsocketClient::disconnect) them. The event
+    /// streams yield such errors as items and keep going instead.
+    ///
+    /// Server pings are answered, and with a [`HeartbeatConfig`] the
+    /// connection is closed and treated as dropped when pings go unanswered.
+    ///
+    /// Only one `listen`, `events` or `feed_events` consumer can run at a
+    /// time; a second one fails with [`VyperError::WebsocketError`].
     pub async fn listen(&self) -> Result<(), VyperError> {
-        let mut conn_guard = self.conn.lock().await;
-        let feed_type_guard = self.current_feed_type.lock().await;
-
-        if conn_guard.is_none() {
-            return Err(VyperError::WebsocketError {
-                message: "Not connected".to_string(),
-                status_code: None,
-                connection_info: None,
-            });
-        }
-
-        while let Ok(msg) = conn_guard.as_mut().unwrap().receive().await {
-            let raw_data: Value = serde_json::from_str(&msg).map_err(|e| VyperError::DeserializeError(e))?;
-            let converted_data = self.convert_message(&raw_data, &feed_type_guard).await;
-
-            if let Err(_) = converted_data {
-                continue;
-            }
-
+        let events = self.start_drivers().await?;
+        let mut events = events.try_lock().map_err(|_| already_listening())?;
+        while let Some((_, event)) = events.recv().await {
+            let data = match event? {
+                WsEvent::TokenEvent(pair) | WsEvent::MigrationEvent(pair) => serde_json::to_value(pair)?,
+                WsEvent::WalletEvent(action) => serde_json::to_value(action)?,
+                WsEvent::Control(_) | WsEvent::Connection(_) => continue,
+            };
             if let Some(ref handler) = self.message_handler {
                 let mut handler = handler.lock().await;
-                handler(converted_data.unwrap());
+                handler(data);
             }
         }
-
         Ok(())
     }
 
-    async fn convert_message(&self, data: &Value, feed_type: &Option<FeedType>) -> Result<Value, VyperError> {
-        if feed_type.is_none() {
-            return Err(VyperError::WebsocketError {
-                message: "Feed type is not set".to_string(),
-                status_code: None,
-                connection_info: None,
-            });
+    /// Typed alternative to [`listen`](VyperWebsocketClient::listen): the
+    /// stream yields every message of the connected feeds as a [`WsEvent`],
+    /// along with connection events, and ends where `listen` would return.
+    ///
+    /// A message that is not valid JSON is yielded as an error and the
+    /// stream carries on. Only one `listen`, `events` or `feed_events`
+    /// consumer can run at a time; a second stream yields a single
+    /// [`VyperError::WebsocketError`] and ends.
+    ///
+    /// ```no_run
+    /// # async fn run(ws_client: vyper_client_rs::websocket::VyperWebsocketClient) -> Result<(), vyper_client_rs::errors::VyperError> {
+    /// use futures_util::StreamExt;
+    /// use vyper_client_rs::websocket::WsEvent;
+    ///
+    /// let mut events = ws_client.events();
+    /// while let Some(event) = events.next().await {
+    ///     match event? {
+    ///         WsEvent::TokenEvent(pair) => println!("{} {}", pair.symbol, pair.token_price_usd),
+    ///         WsEvent::Connection(event) => println!("{:?}", event),
+    ///         _ => {}
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn events(&self) -> BoxStream<'_, Result<WsEvent, VyperError>> {
+        self.feed_events().map(|event| event.map(|event| event.event)).boxed()
+    }
+
+    /// Like [`events`](VyperWebsocketClient::events), with every event tagged
+    /// with the feed it came from.
+    pub fn feed_events(&self) -> BoxStream<'_, Result<FeedEvent, VyperError>> {
+        stream::once(self.start_drivers())
+            .flat_map(|events| match events {
+                Ok(events) => match events.try_lock_owned() {
+                    Ok(events) => stream::unfold(events, |mut events| async move {
+                        let (feed_type, event) = events.recv().await?;
+                        Some((event.map(|event| FeedEvent { feed_type, event }), events))
+                    })
+                    .boxed(),
+                    Err(_) => stream::iter(vec![Err(already_listening())]).boxed(),
+                },
+                Err(e) => stream::iter(vec![Err(e)]).boxed(),
+            })
+            .boxed()
+    }
+
+    /// Starts drivers for every pending connection and returns the merged
+    /// event receiver.
+    async fn start_drivers(&self) -> Result<Arc<Mutex<mpsc::Receiver<TaggedEvent>>>, VyperError> {
+        let mut drivers = self.drivers.lock().await;
+        let pending: Vec<_> = self.conn.lock().await.drain().collect();
+        for (feed_type, conn) in pending {
+            let driver = self.spawn_driver(feed_type.clone(), conn).await;
+            drivers.insert(feed_type, driver);
         }
-    
-        match feed_type.as_ref().unwrap() {
-            FeedType::WalletEvents => {
-                let chain_action: ChainAction = serde_json::from_value(data.clone()).map_err(|e| VyperError::DeserializeError(e))?;
-                Ok(serde_json::to_value(chain_action).map_err(|e| VyperError::DeserializeError(e))?)
-            },
-            FeedType::MigrationEvents | FeedType::TokenEvents => {
-                let token_pair: TokenPair = serde_json::from_value(data.clone()).map_err(|e| VyperError::DeserializeError(e))?;
-                Ok(serde_json::to_value(token_pair).map_err(|e| VyperError::DeserializeError(e))?)
-            },
+        drivers.retain(|_, driver| !driver.commands.is_closed());
+        if drivers.is_empty() {
+            return Err(not_connected());
         }
+        Ok(self.events.lock().await.receiver.clone())
     }
 
-    pub async fn disconnect(&self) -> Result<(), VyperError> {
-        let mut conn_guard = self.conn.lock().await;
-        if let Some(ref mut ws) = conn_guard.as_mut() {
+    /// Returns the driver of `feed_type`, starting it if the connection is
+    /// still pending.
+    async fn driver(&self, feed_type: &FeedType) -> Result<DriverHandle, VyperError> {
+        let mut drivers = self.drivers.lock().await;
+        if let Some(driver) = drivers.get(feed_type) {
+            return Ok(driver.clone());
+        }
+
+        let conn = self.conn.lock().await.remove(feed_type).ok_or_else(|| VyperError::WebsocketError {
+            message: format!("Not connected to {}", feed_type),
+            status_code: None,
+            connection_info: None,
+        })?;
+        let driver = self.spawn_driver(feed_type.clone(), conn).await;
+        drivers.insert(feed_type.clone(), driver.clone());
+        Ok(driver)
+    }
+
+    async fn spawn_driver(&self, feed_type: FeedType, conn: Box<dyn WebSocketConnection>) -> DriverHandle {
+        let (commands, command_receiver) = mpsc::unbounded_channel();
+        let driver = Driver {
+            conn,
+            url: self.feed_url(&feed_type),
+            feed_type,
+            reconnect_policy: self.reconnect_policy.clone(),
+            heartbeat_config: self.heartbeat.clone(),
+            heartbeat: self.heartbeat.clone().map(Heartbeat::new),
+            connection_handler: self.connection_handler.clone(),
+            subscriptions: Subscriptions::default(),
+            commands: command_receiver,
+            events: self.events.lock().await.sender(),
+            pending: VecDeque::new(),
+            state: DriverState::Connected,
+        };
+        tokio::spawn(driver.run());
+        DriverHandle { commands }
+    }
+
+    async fn command<F>(&self, feed_type: &FeedType, command: F) -> Result<(), VyperError>
+    where
+        F: FnOnce(oneshot::Sender<Result<(), VyperError>>) -> Command,
+    {
+        let driver = self.driver(feed_type).await?;
+        let (reply, response) = oneshot::channel();
+        driver.commands.send(command(reply)).map_err(|_| not_connected())?;
+        response.await.map_err(|_| not_connected())?
+    }
+
+    /// Closes the driven connection to `feed_type`, if any.
+    async fn stop_driver(&self, feed_type: &FeedType) -> Result<(), VyperError> {
+        let driver = match self.drivers.lock().await.remove(feed_type) {
+            Some(driver) => driver,
+            None => return Ok(()),
+        };
+        let (reply, response) = oneshot::channel();
+        if driver.commands.send(Command::Close { reply }).is_err() {
+            return Ok(());
+        }
+        response.await.unwrap_or(Ok(()))
+    }
+
+    /// Closes the connection to `feed_type`. Other feeds are not affected.
+    pub async fn disconnect_feed(&self, feed_type: FeedType) -> Result<(), VyperError> {
+        let stopped = self.stop_driver(&feed_type).await;
+        let conn = self.conn.lock().await.remove(&feed_type);
+        if let Some(mut ws) = conn {
             ws.close().await.map_err(|e| VyperError::WebsocketError {
                 message: e.to_string(),
                 status_code: None,
@@ -203,17 +612,30 @@ impl VyperWebsocketClient {
             })?;
         }
 
-        *conn_guard = None;
-        let mut feed_type_guard = self.current_feed_type.lock().await;
-        *feed_type_guard = None;
+        stopped.map_err(|e| VyperError::WebsocketError {
+            message: e.to_string(),
+            status_code: None,
+            connection_info: None,
+        })
+    }
 
-        Ok(())
+    /// Closes every connection. A consumer that is listening sees the end
+    /// of the stream.
+    pub async fn disconnect(&self) -> Result<(), VyperError> {
+        let mut result = Ok(());
+        for feed_type in self.connected_feeds().await {
+            let closed = self.disconnect_feed(feed_type).await;
+            if result.is_ok() {
+                result = closed;
+            }
+        }
+        result
     }
 
+    /// Sends a WebSocket Ping frame on every connection.
     pub async fn ping(&self) -> Result<(), VyperError> {
-        let mut conn_guard = self.conn.lock().await;
-        if let Some(ref mut ws) = conn_guard.as_mut() {
-            ws.send("ping").await.map_err(|e| VyperError::WebsocketError {
+        for feed_type in self.connected_feeds().await {
+            self.command(&feed_type, |reply| Command::Ping { reply }).await.map_err(|e| VyperError::WebsocketError {
                 message: e.to_string(),
                 status_code: None,
                 connection_info: None,
@@ -222,12 +644,344 @@ impl VyperWebsocketClient {
         Ok(())
     }
 
+    /// Sets the handler [`listen`](VyperWebsocketClient::listen) passes
+    /// messages to.
     pub fn set_message_handler<F>(&mut self, handler: F)
     where
         F: Fn(Value) + Send + Sync + 'static,
     {
         self.message_handler = Some(Arc::new(Mutex::new(handler)));
     }
+
+    /// Sets a handler for [`ConnectionEvent`]s such as reconnects. It applies
+    /// to connections driven after it is set.
+    pub fn set_connection_handler<F>(&mut self, handler: F)
+    where
+        F: Fn(ConnectionEvent) + Send + Sync + 'static,
+    {
+        self.connection_handler = Some(Arc::new(Mutex::new(handler)));
+    }
+}
+
+/// Requests from [`VyperWebsocketClient`] to the task driving its connection.
+enum Command {
+    /// Send a message and, once sent, track it as a subscription change.
+    Send {
+        message: Value,
+        reply: oneshot::Sender<Result<(), VyperError>>,
+    },
+    Ping {
+        reply: oneshot::Sender<Result<(), VyperError>>,
+    },
+    Close {
+        reply: oneshot::Sender<Result<(), VyperError>>,
+    },
+}
+
+/// Number of events buffered for the consumer before the driver stops
+/// reading from the socket.
+const EVENT_BUFFER: usize = 256;
+
+fn not_connected() -> VyperError {
+    VyperError::WebsocketError {
+        message: "Not connected".to_string(),
+        status_code: None,
+        connection_info: None,
+    }
+}
+
+fn already_listening() -> VyperError {
+    VyperError::WebsocketError {
+        message: "Already listening".to_string(),
+        status_code: None,
+        connection_info: None,
+    }
+}
+
+fn control_frames_unsupported() -> VyperError {
+    VyperError::WebsocketError {
+        message: "Ping and pong frames are not supported by this connection".to_string(),
+        status_code: None,
+        connection_info: None,
+    }
+}
+
+enum DriverState {
+    Connected,
+    /// Waiting until `at` to make reconnect `attempt`.
+    Reconnecting {
+        attempt: u32,
+        at: Instant,
+        last_error: VyperError,
+    },
+    /// The connection is gone; queued events are delivered before the task
+    /// exits.
+    Closed,
+}
+
+/// Owns one connection and runs until it is closed or lost for good.
+///
+/// Receiving, heartbeats, reconnects and client commands are multiplexed
+/// on a single task, so subscription changes never wait for the consumer.
+struct Driver {
+    conn: Box<dyn WebSocketConnection>,
+    feed_type: FeedType,
+    url: String,
+    reconnect_policy: Option<ReconnectPolicy>,
+    heartbeat_config: Option<HeartbeatConfig>,
+    heartbeat: Option<Heartbeat>,
+    connection_handler: Option<ConnectionHandler>,
+    subscriptions: Subscriptions,
+    commands: mpsc::UnboundedReceiver<Command>,
+    events: mpsc::Sender<TaggedEvent>,
+    /// Events waiting for room in `events`. Nothing is received while this
+    /// is non-empty.
+    pending: VecDeque<Result<WsEvent, VyperError>>,
+    state: DriverState,
+}
+
+impl Driver {
+    async fn run(mut self) {
+        loop {
+            if matches!(self.state, DriverState::Closed) && self.pending.is_empty() {
+                return;
+            }
+            let connected = matches!(self.state, DriverState::Connected);
+            // Reads pause while the consumer is behind.
+            let reading = connected && self.pending.is_empty();
+            let reconnect_at = match self.state {
+                DriverState::Reconnecting { at, .. } => Some(at),
+                _ => None,
+            };
+            // Reserving on a clone keeps the permit from borrowing `self`.
+            let events = self.events.clone();
+
+            tokio::select! {
+                biased;
+                command = self.commands.recv() => match command {
+                    Some(Command::Send { message, reply }) => {
+                        let result = match self.state {
+                            DriverState::Connected => self.conn.send(&message.to_string()).await,
+                            // Replayed once the connection is back.
+                            DriverState::Reconnecting { .. } => Ok(()),
+                            DriverState::Closed => Err(not_connected()),
+                        };
+                        if result.is_ok() {
+                            self.subscriptions.record(message);
+                        }
+                        let _ = reply.send(result);
+                    }
+                    Some(Command::Ping { reply }) => {
+                        let result = match self.state {
+                            DriverState::Connected => self.conn.send_ping(&[]).await,
+                            _ => Err(not_connected()),
+                        };
+                        let _ = reply.send(result);
+                    }
+                    Some(Command::Close { reply }) => {
+                        let result = match self.state {
+                            DriverState::Connected => self.conn.close().await,
+                            _ => Ok(()),
+                        };
+                        let _ = reply.send(result);
+                        return;
+                    }
+                    // The client is gone.
+                    None => {
+                        if connected {
+                            let _ = self.conn.close().await;
+                        }
+                        return;
+                    }
+                },
+                permit = events.reserve(), if !self.pending.is_empty() => match permit {
+                    Ok(permit) => {
+                        let event = self.pending.pop_front().expect("checked by the branch condition");
+                        permit.send((self.feed_type.clone(), event));
+                        if self.pending.is_empty() {
+                            if let Some(ref mut heartbeat) = self.heartbeat {
+                                heartbeat.resume(Instant::now());
+                            }
+                        }
+                    }
+                    Err(_) => return,
+                },
+                frame = self.conn.receive_frame(), if reading => match frame {
+                    Ok(frame) => self.handle_frame(frame).await,
+                    Err(e) => self.disconnected(e).await,
+                },
+                () = heartbeat_due(self.heartbeat.as_ref()), if reading => self.beat().await,
+                () = tokio::time::sleep_until(reconnect_at.unwrap_or_else(Instant::now)), if reconnect_at.is_some() => {
+                    self.reconnect().await
+                },
+            }
+        }
+    }
+
+    async fn handle_frame(&mut self, frame: Frame) {
+        if let Some(ref mut heartbeat) = self.heartbeat {
+            heartbeat.alive();
+        }
+        match frame {
+            Frame::Text(msg) => self.pending.push_back(parse_event(&msg, &self.feed_type)),
+            Frame::Ping(payload) => {
+                if let Err(e) = self.conn.send_pong(&payload).await {
+                    self.disconnected(e).await;
+                }
+            }
+            Frame::Pong(_) => {}
+        }
+    }
+
+    async fn beat(&mut self) {
+        let heartbeat = self.heartbeat.as_mut().expect("only due when enabled");
+        let now = Instant::now();
+        if heartbeat.expired(now) {
+            let error = VyperError::WebsocketError {
+                message: format!("No pong received within {:?}", heartbeat.config.pong_timeout),
+                status_code: None,
+                connection_info: None,
+            };
+            let _ = self.conn.close().await;
+            self.disconnected(error).await;
+            return;
+        }
+        match self.conn.send_ping(&[]).await {
+            Ok(()) => heartbeat.ping_sent(now),
+            Err(e) => self.disconnected(e).await,
+        }
+    }
+
+    async fn disconnected(&mut self, error: VyperError) {
+        self.heartbeat = None;
+        self.emit(ConnectionEvent::Disconnected {
+            feed_type: self.feed_type.clone(),
+            reason: error.to_string(),
+        })
+        .await;
+        self.schedule_reconnect(1, error).await;
+    }
+
+    async fn schedule_reconnect(&mut self, attempt: u32, last_error: VyperError) {
+        let feed_type = self.feed_type.clone();
+        let policy = match self.reconnect_policy {
+            Some(ref policy) => policy,
+            None => {
+                self.state = DriverState::Closed;
+                return;
+            }
+        };
+        match policy.next_delay(attempt) {
+            Some(delay) => {
+                self.state = DriverState::Reconnecting {
+                    attempt,
+                    at: Instant::now() + delay,
+                    last_error,
+                };
+                self.emit(ConnectionEvent::Reconnecting {
+                    feed_type,
+                    attempt,
+                    delay,
+                })
+                .await;
+            }
+            None => {
+                self.state = DriverState::Closed;
+                self.emit(ConnectionEvent::ReconnectFailed {
+                    feed_type,
+                    attempts: attempt - 1,
+                })
+                .await;
+                self.pending.push_back(Err(last_error));
+            }
+        }
+    }
+
+    async fn reconnect(&mut self) {
+        let (attempt, last_error) = match std::mem::replace(&mut self.state, DriverState::Closed) {
+            DriverState::Reconnecting { attempt, last_error, .. } => (attempt, last_error),
+            state => {
+                self.state = state;
+                return;
+            }
+        };
+        drop(last_error);
+        match self.resubscribe().await {
+            Ok(()) => {
+                self.state = DriverState::Connected;
+                self.heartbeat = self.heartbeat_config.clone().map(Heartbeat::new);
+                let feed_type = self.feed_type.clone();
+                self.emit(ConnectionEvent::Reconnected { feed_type, attempt }).await;
+            }
+            Err(e) => self.schedule_reconnect(attempt + 1, e).await,
+        }
+    }
+
+    /// Connects again and replays the active subscriptions.
+    async fn resubscribe(&mut self) -> Result<(), VyperError> {
+        self.conn.connect(&self.url).await?;
+        for message in self.subscriptions.replay_messages()? {
+            self.conn.send(&message).await?;
+        }
+        Ok(())
+    }
+
+    /// Passes `event` to the connection handler and queues it for the consumer.
+    async fn emit(&mut self, event: ConnectionEvent) {
+        if let Some(ref handler) = self.connection_handler {
+            let mut handler = handler.lock().await;
+            handler(event.clone());
+        }
+        self.pending.push_back(Ok(WsEvent::Connection(event)));
+    }
+}
+
+/// The client's side of a running [`Driver`].
+#[derive(Clone)]
+struct DriverHandle {
+    commands: mpsc::UnboundedSender<Command>,
+}
+
+type TaggedEvent = (FeedType, Result<WsEvent, VyperError>);
+
+/// The channel every driver sends its events into.
+///
+/// The client only keeps a weak sender, so the receiver sees the end of the
+/// stream once the last driver has exited. The next driver then starts a new
+/// channel.
+struct EventChannel {
+    sender: Option<mpsc::WeakSender<TaggedEvent>>,
+    receiver: Arc<Mutex<mpsc::Receiver<TaggedEvent>>>,
+}
+
+impl EventChannel {
+    fn closed() -> Self {
+        let (_, receiver) = mpsc::channel(1);
+        Self {
+            sender: None,
+            receiver: Arc::new(Mutex::new(receiver)),
+        }
+    }
+
+    fn sender(&mut self) -> mpsc::Sender<TaggedEvent> {
+        if let Some(sender) = self.sender.as_ref().and_then(mpsc::WeakSender::upgrade) {
+            return sender;
+        }
+
+        let (sender, receiver) = mpsc::channel(EVENT_BUFFER);
+        // Carry over whatever the last consumer has not read yet. Both
+        // channels hold `EVENT_BUFFER` events, so everything fits. If a
+        // consumer still holds the old receiver, nothing is lost either: it
+        // drains the remaining events itself before its stream ends.
+        if let Ok(mut previous) = self.receiver.try_lock() {
+            while let Ok(event) = previous.try_recv() {
+                let _ = sender.try_send(event);
+            }
+        }
+        self.sender = Some(sender.downgrade());
+        self.receiver = Arc::new(Mutex::new(receiver));
+        sender
+    }
 }
 
 struct WebSocketImpl {
@@ -240,6 +994,23 @@ impl WebSocketImpl {
             ws_stream: None,
         }
     }
+
+    async fn send_message(&mut self, message: Message) -> Result<(), VyperError> {
+        if let Some(ws_stream) = &mut self.ws_stream {
+            ws_stream.send(message).await.map_err(|e| VyperError::WebsocketError {
+                message: e.to_string(),
+                status_code: None,
+                connection_info: None,
+            })?;
+            Ok(())
+        } else {
+            Err(VyperError::WebsocketError {
+                message: "WebSocket is not connected".to_string(),
+                status_code: None,
+                connection_info: None,
+            })
+        }
+    }
 }
 
 #[async_trait]
@@ -255,28 +1026,30 @@ impl WebSocketConnection for WebSocketImpl {
     }
 
     async fn send(&mut self, data: &str) -> Result<(), VyperError> {
-        if let Some(ws_stream) = &mut self.ws_stream {
-            ws_stream.send(Message::Text(data.to_string())).await.map_err(|e| VyperError::WebsocketError {
-                message: e.to_string(),
-                status_code: None,
-                connection_info: None,
-            })?;
-            Ok(())
-        } else {
-            Err(VyperError::WebsocketError {
-                message: "WebSocket is not connected".to_string(),
-                status_code: None,
-                connection_info: None,
-            })
-        }
+        self.send_message(Message::Text(data.to_string())).await
     }
 
     async fn receive(&mut self) -> Result<String, VyperError> {
+        loop {
+            if let Frame::Text(text) = self.receive_frame().await? {
+                return Ok(text);
+            }
+        }
+    }
+
+    async fn receive_frame(&mut self) -> Result<Frame, VyperError> {
         if let Some(ws_stream) = &mut self.ws_stream {
             if let Some(message) = ws_stream.next().await {
                 match message {
                     Ok(message) => match message {
-                        Message::Text(text) => Ok(text),
+                        Message::Text(text) => Ok(Frame::Text(text)),
+                        Message::Ping(payload) => Ok(Frame::Ping(payload)),
+                        Message::Pong(payload) => Ok(Frame::Pong(payload)),
+                        Message::Close(_) => Err(VyperError::WebsocketError {
+                            message: "WebSocket closed by server".to_string(),
+                            status_code: None,
+                            connection_info: None,
+                        }),
                         _ => Err(VyperError::WebsocketError {
                             message: "Received non-text message".to_string(),
                             status_code: None,
@@ -305,6 +1078,14 @@ impl WebSocketConnection for WebSocketImpl {
         }
     }
 
+    async fn send_ping(&mut self, payload: &[u8]) -> Result<(), VyperError> {
+        self.send_message(Message::Ping(payload.to_vec())).await
+    }
+
+    async fn send_pong(&mut self, payload: &[u8]) -> Result<(), VyperError> {
+        self.send_message(Message::Pong(payload.to_vec())).await
+    }
+
     async fn close(&mut self) -> Result<(), VyperError> {
         if let Some(ws_stream) = &mut self.ws_stream {
             ws_stream.close(None).await.map_err(|e| VyperError::WebsocketError {
@@ -339,6 +1120,7 @@ mod tests {
             async fn send(&mut self, data: &str) -> Result<(), VyperError>;
             async fn receive(&mut self) -> Result<String, VyperError>;
             async fn close(&mut self) -> Result<(), VyperError>;
+            async fn send_ping(&mut self, payload: &[u8]) -> Result<(), VyperError>;
         }
     }    
 
@@ -351,25 +1133,23 @@ mod tests {
 
     #[tokio::test]
     async fn test_connect() {
-        let mut mock_ws = MockWebSocket::new();
-        
-        mock_ws.expect_connect()
-            .with(eq("wss://api.vyper.trade/api/v1/ws/token-events?apiKey=test_api_key"))
-            .times(1)
-            .returning(|_| Ok(()));
+        let (conn, calls) = FakeConnection::new(false, Vec::new());
 
         let client = VyperWebsocketClient::new("test_api_key".to_string());
 
         {
             let mut conn_guard = client.conn.lock().await;
-            *conn_guard = Some(Box::new(mock_ws));
+            conn_guard.insert(FeedType::TokenEvents, Box::new(conn));
         }
 
         let result = client.connect(FeedType::TokenEvents).await;
         assert!(result.is_ok());
 
-        let feed_type_guard = client.current_feed_type.lock().await;
-        assert_eq!(*feed_type_guard, Some(FeedType::TokenEvents));
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec!["connect wss://api.vyper.trade/api/v1/ws/token-events?apiKey=test_api_key"]
+        );
+        assert_eq!(client.connected_feeds().await, vec![FeedType::TokenEvents]);
     }
 
     #[tokio::test]
@@ -379,7 +1159,7 @@ mod tests {
             .returning(|| Ok(()));
 
         let client = VyperWebsocketClient::new("test_api_key".to_string());
-        *client.conn.lock().await = Some(Box::new(mock_ws));
+        client.conn.lock().await.insert(FeedType::TokenEvents, Box::new(mock_ws));
         
         let result = client.disconnect().await;
         assert!(result.is_ok());
@@ -390,10 +1170,11 @@ mod tests {
         let mut mock_ws = MockWebSocket::new();
         mock_ws.expect_send()
             .returning(|_| Ok(()));
+        mock_ws.expect_receive()
+            .returning(|| Err("Connection closed".into()));
 
         let client = VyperWebsocketClient::new("test_api_key".to_string());
-        *client.conn.lock().await = Some(Box::new(mock_ws));
-        *client.current_feed_type.lock().await = Some(FeedType::TokenEvents);
+        client.conn.lock().await.insert(FeedType::TokenEvents, Box::new(mock_ws));
 
         let message = TokenSubscriptionMessage {
             action: SubscriptionMessageType::Subscribe,
@@ -409,10 +1190,11 @@ mod tests {
         let mut mock_ws = MockWebSocket::new();
         mock_ws.expect_send()
             .returning(|_| Ok(()));
+        mock_ws.expect_receive()
+            .returning(|| Err("Connection closed".into()));
 
         let client = VyperWebsocketClient::new("test_api_key".to_string());
-        *client.conn.lock().await = Some(Box::new(mock_ws));
-        *client.current_feed_type.lock().await = Some(FeedType::TokenEvents);
+        client.conn.lock().await.insert(FeedType::TokenEvents, Box::new(mock_ws));
 
         let message = TokenSubscriptionMessage {
             action: SubscriptionMessageType::Unsubscribe,
@@ -426,17 +1208,52 @@ mod tests {
     #[tokio::test]
     async fn test_ping() {
         let mut mock_ws = MockWebSocket::new();
-        mock_ws.expect_send()
-            .with(eq("ping"))
+        mock_ws.expect_send_ping()
+            .withf(|payload| payload.is_empty())
+            .times(1)
             .returning(|_| Ok(()));
+        mock_ws.expect_receive()
+            .returning(|| Err("Connection closed".into()));
 
         let client = VyperWebsocketClient::new("test_api_key".to_string());
-        *client.conn.lock().await = Some(Box::new(mock_ws));
+        client.conn.lock().await.insert(FeedType::TokenEvents, Box::new(mock_ws));
         
         let result = client.ping().await;
         assert!(result.is_ok());
     }
 
+    /// Implements only the required methods, as pre-heartbeat connections do.
+    struct TextOnlyConnection;
+
+    #[async_trait]
+    impl WebSocketConnection for TextOnlyConnection {
+        async fn connect(&mut self, _url: &str) -> Result<(), VyperError> {
+            Ok(())
+        }
+
+        async fn send(&mut self, _data: &str) -> Result<(), VyperError> {
+            Ok(())
+        }
+
+        async fn receive(&mut self) -> Result<String, VyperError> {
+            std::future::pending().await
+        }
+
+        async fn close(&mut self) -> Result<(), VyperError> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ping_unsupported_by_default() {
+        let client = VyperWebsocketClient::new("test_api_key".to_string());
+        client.conn.lock().await.insert(FeedType::TokenEvents, Box::new(TextOnlyConnection));
+
+        let error = client.ping().await.unwrap_err();
+        assert!(error.to_string().contains("not supported"));
+        assert_eq!(client.connected_feeds().await, vec![FeedType::TokenEvents]);
+    }
+
     #[tokio::test]
     async fn test_listen() {
         let mut mock_ws = MockWebSocket::new();
@@ -476,8 +1293,7 @@ mod tests {
             .returning(|| Err("Connection closed".into()));
 
         let mut client = VyperWebsocketClient::new("test_api_key".to_string());
-        *client.conn.lock().await = Some(Box::new(mock_ws));
-        *client.current_feed_type.lock().await = Some(FeedType::TokenEvents);
+        client.conn.lock().await.insert(FeedType::TokenEvents, Box::new(mock_ws));
         
         let received_message = Arc::new(Mutex::new(false));
         let received_message_clone = received_message.clone();
@@ -509,7 +1325,7 @@ mod tests {
             .returning(|_| Err("Connection failed".into()));
 
         let client = VyperWebsocketClient::new("test_api_key".to_string());
-        *client.conn.lock().await = Some(Box::new(mock_ws));
+        client.conn.lock().await.insert(FeedType::TokenEvents, Box::new(mock_ws));
         
         let result = client.connect(FeedType::TokenEvents).await;
         assert!(result.is_err());
@@ -532,8 +1348,7 @@ mod tests {
             .returning(|_| Ok(()));
 
         let client = VyperWebsocketClient::new("test_api_key".to_string());
-        *client.conn.lock().await = Some(Box::new(mock_ws));
-        *client.current_feed_type.lock().await = Some(FeedType::TokenEvents);
+        client.conn.lock().await.insert(FeedType::TokenEvents, Box::new(mock_ws));
 
         let message = WalletSubscriptionMessage {
             action: SubscriptionMessageType::Subscribe,
@@ -543,4 +1358,517 @@ mod tests {
         let result = client.subscribe(FeedType::WalletEvents, message).await;
         assert!(result.is_err());
     }
-}
\ No newline at end of file
+
+    const TOKEN_PAIR_JSON: &str = r#"{
+        "chainId": 900,
+        "tokenMint": "mint",
+        "name": "Test Token",
+        "symbol": "TEST",
+        "buyTxnCount": 1,
+        "sellTxnCount": 1,
+        "tokenPriceUsd": 1.0,
+        "tokenPriceAsset": 1.0,
+        "volumeUsd": 1.0,
+        "volumeAsset": 1.0,
+        "tokenMarketCapUsd": 1.0,
+        "tokenMarketCapAsset": 1.0,
+        "tokenLiquidityUsd": 1.0,
+        "tokenLiquidityAsset": 1.0,
+        "transactionCount": 2,
+        "contractCreator": "creator",
+        "lpCreator": "creator",
+        "createdTimestamp": 1631234567,
+        "totalSupply": 1.0,
+        "pooledToken": 1.0,
+        "pooledAsset": 1.0,
+        "initialUsdLiquidity": 1.0,
+        "initialAssetLiquidity": 1.0,
+        "priceChangePercent": 0.0,
+        "top10HoldingPercent": 0.0,
+        "lpBurned": false,
+        "tokenType": "SPL",
+        "marketId": "market1"
+    }"#;
+
+    #[test]
+    fn test_subscriptions_track_active_set() {
+        let mut subscriptions = Subscriptions::default();
+        subscriptions.record(serde_json::json!({"action": "subscribe", "types": ["PumpfunTokens", "RaydiumAmmTokens"]}));
+        subscriptions.record(serde_json::json!({"action": "subscribe", "wallets": ["w1", "w2"]}));
+        subscriptions.record(serde_json::json!({"action": "unsubscribe", "types": ["PumpfunTokens"]}));
+        subscriptions.record(serde_json::json!({"action": "unsubscribe", "wallets": ["w1"]}));
+        subscriptions.record(serde_json::json!("ping"));
+
+        assert_eq!(
+            subscriptions.replay_messages().unwrap(),
+            vec![
+                r#"{"action":"subscribe","types":["RaydiumAmmTokens"]}"#.to_string(),
+                r#"{"action":"subscribe","wallets":["w2"]}"#.to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reconnect_policy_backoff() {
+        let policy = ReconnectPolicy {
+            max_attempts: Some(3),
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(250),
+            jitter: 0.0,
+        };
+        assert_eq!(policy.next_delay(1), Some(Duration::from_millis(100)));
+        assert_eq!(policy.next_delay(2), Some(Duration::from_millis(200)));
+        assert_eq!(policy.next_delay(3), Some(Duration::from_millis(250)));
+        assert_eq!(policy.next_delay(4), None);
+        assert!(ReconnectPolicy::default().next_delay(1_000).is_some());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_listen_reconnects_and_resubscribes() {
+        let url = "wss://api.vyper.trade/api/v1/ws/token-events?apiKey=test_api_key";
+        let subscribe = r#"{"action":"subscribe","types":["PumpfunTokens"]}"#;
+        let mut seq = mockall::Sequence::new();
+        let mut mock_ws = MockWebSocket::new();
+        mock_ws.expect_send().with(eq(subscribe)).times(1).in_sequence(&mut seq).returning(|_| Ok(()));
+        mock_ws.expect_receive().times(1).in_sequence(&mut seq).returning(|| Err("connection reset".into()));
+        mock_ws.expect_connect().with(eq(url)).times(1).in_sequence(&mut seq).returning(|_| Err("refused".into()));
+        mock_ws.expect_connect().with(eq(url)).times(1).in_sequence(&mut seq).returning(|_| Ok(()));
+        mock_ws.expect_send().with(eq(subscribe)).times(1).in_sequence(&mut seq).returning(|_| Ok(()));
+        mock_ws.expect_receive().times(1).in_sequence(&mut seq).returning(|| Ok(TOKEN_PAIR_JSON.to_string()));
+        mock_ws.expect_receive().times(1).in_sequence(&mut seq).returning(|| Err("connection reset".into()));
+        mock_ws.expect_connect().times(2).in_sequence(&mut seq).returning(|_| Err("refused".into()));
+
+        let mut client = VyperWebsocketClient::new("test_api_key".to_string()).with_reconnect_policy(ReconnectPolicy {
+            max_attempts: Some(2),
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+            jitter: 0.0,
+        });
+        client.conn.lock().await.insert(FeedType::TokenEvents, Box::new(mock_ws));
+
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let events_clone = events.clone();
+        client.set_connection_handler(move |event| events_clone.lock().unwrap().push(event));
+        let messages = Arc::new(std::sync::Mutex::new(0));
+        let messages_clone = messages.clone();
+        client.set_message_handler(move |_| *messages_clone.lock().unwrap() += 1);
+
+        client
+            .subscribe(
+                FeedType::TokenEvents,
+                TokenSubscriptionMessage {
+                    action: SubscriptionMessageType::Subscribe,
+                    types: vec![SubscriptionType::PumpfunTokens],
+                },
+            )
+            .await
+            .unwrap();
+
+        let result = client.listen().await;
+        assert!(matches!(result, Err(VyperError::WebsocketError { ref message, .. }) if message == "refused"));
+        assert_eq!(*messages.lock().unwrap(), 1);
+
+        let feed_type = FeedType::TokenEvents;
+        let disconnected = ConnectionEvent::Disconnected {
+            feed_type: feed_type.clone(),
+            reason: "Websocket error: connection reset".to_string(),
+        };
+        let reconnecting = |attempt, millis| ConnectionEvent::Reconnecting {
+            feed_type: feed_type.clone(),
+            attempt,
+            delay: Duration::from_millis(millis),
+        };
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                disconnected.clone(),
+                reconnecting(1, 100),
+                reconnecting(2, 200),
+                ConnectionEvent::Reconnected {
+                    feed_type: feed_type.clone(),
+                    attempt: 2,
+                },
+                disconnected,
+                reconnecting(1, 100),
+                reconnecting(2, 200),
+                ConnectionEvent::ReconnectFailed {
+                    feed_type: feed_type.clone(),
+                    attempts: 2,
+                },
+            ]
+        );
+    }
+
+    /// Connection fed from a channel, so `receive_frame` can wait like a real
+    /// socket. Every call is logged to `calls`.
+    struct FakeConnection {
+        frames: tokio::sync::mpsc::UnboundedReceiver<Result<Frame, VyperError>>,
+        replies: tokio::sync::mpsc::UnboundedSender<Result<Frame, VyperError>>,
+        answer_pings: bool,
+        calls: Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    impl FakeConnection {
+        fn new(answer_pings: bool, frames: Vec<Result<Frame, VyperError>>) -> (Self, Arc<std::sync::Mutex<Vec<String>>>) {
+            let (replies, receiver) = tokio::sync::mpsc::unbounded_channel();
+            for frame in frames {
+                replies.send(frame).unwrap();
+            }
+            let calls = Arc::new(std::sync::Mutex::new(Vec::new()));
+            let conn = Self {
+                frames: receiver,
+                replies,
+                answer_pings,
+                calls: calls.clone(),
+            };
+            (conn, calls)
+        }
+
+        fn log(&self, call: String) {
+            self.calls.lock().unwrap().push(call);
+        }
+    }
+
+    #[async_trait]
+    impl WebSocketConnection for FakeConnection {
+        async fn connect(&mut self, url: &str) -> Result<(), VyperError> {
+            self.log(format!("connect {}", url));
+            Ok(())
+        }
+
+        async fn send(&mut self, data: &str) -> Result<(), VyperError> {
+            self.log(format!("send {}", data));
+            Ok(())
+        }
+
+        async fn receive(&mut self) -> Result<String, VyperError> {
+            unreachable!("listen receives frames")
+        }
+
+        async fn receive_frame(&mut self) -> Result<Frame, VyperError> {
+            match self.frames.recv().await {
+                Some(frame) => frame,
+                None => std::future::pending().await,
+            }
+        }
+
+        async fn close(&mut self) -> Result<(), VyperError> {
+            self.log("close".to_string());
+            Ok(())
+        }
+
+        async fn send_ping(&mut self, payload: &[u8]) -> Result<(), VyperError> {
+            self.log("ping".to_string());
+            if self.answer_pings {
+                self.replies.send(Ok(Frame::Pong(payload.to_vec()))).unwrap();
+            }
+            Ok(())
+        }
+
+        async fn send_pong(&mut self, payload: &[u8]) -> Result<(), VyperError> {
+            self.log(format!("pong {}", String::from_utf8_lossy(payload)));
+            Ok(())
+        }
+    }
+
+    fn heartbeat() -> HeartbeatConfig {
+        HeartbeatConfig {
+            interval: Duration::from_secs(10),
+            pong_timeout: Duration::from_secs(5),
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_heartbeat_keeps_live_connection() {
+        let (conn, calls) = FakeConnection::new(true, vec![Ok(Frame::Ping(b"hello".to_vec()))]);
+        let client = VyperWebsocketClient::new("test_api_key".to_string()).with_heartbeat(heartbeat());
+        client.conn.lock().await.insert(FeedType::TokenEvents, Box::new(conn));
+
+        let result = tokio::time::timeout(Duration::from_secs(35), client.listen()).await;
+        assert!(result.is_err(), "listen should still be running");
+        assert_eq!(*calls.lock().unwrap(), vec!["pong hello", "ping", "ping", "ping"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_heartbeat_closes_dead_connection() {
+        let (conn, calls) = FakeConnection::new(false, Vec::new());
+        let mut client = VyperWebsocketClient::new("test_api_key".to_string()).with_heartbeat(heartbeat());
+        client.conn.lock().await.insert(FeedType::WalletEvents, Box::new(conn));
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let events_clone = events.clone();
+        client.set_connection_handler(move |event| events_clone.lock().unwrap().push(event));
+
+        let started = Instant::now();
+        client.listen().await.unwrap();
+        assert_eq!(started.elapsed(), Duration::from_secs(15));
+        assert_eq!(*calls.lock().unwrap(), vec!["ping", "close"]);
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![ConnectionEvent::Disconnected {
+                feed_type: FeedType::WalletEvents,
+                reason: "Websocket error: No pong received within 5s".to_string(),
+            }]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_heartbeat_paused_while_consumer_is_behind() {
+        let backlog = EVENT_BUFFER + 10;
+        let frames = (0..backlog).map(|_| Ok(Frame::Text(TOKEN_PAIR_JSON.to_string()))).collect();
+        let (conn, calls) = FakeConnection::new(false, frames);
+        let client = VyperWebsocketClient::new("test_api_key".to_string()).with_heartbeat(heartbeat());
+        client.conn.lock().await.insert(FeedType::TokenEvents, Box::new(conn));
+        client.connect(FeedType::TokenEvents).await.unwrap();
+
+        // Nobody reads, so the driver stops reading too and must not ping.
+        tokio::time::sleep(Duration::from_secs(60)).await;
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec!["connect wss://api.vyper.trade/api/v1/ws/token-events?apiKey=test_api_key"]
+        );
+
+        let mut events = client.events();
+        for _ in 0..backlog {
+            assert!(matches!(events.next().await, Some(Ok(WsEvent::TokenEvent(_)))));
+        }
+
+        // Once caught up, the heartbeat restarts and the silent server is dropped.
+        let started = Instant::now();
+        assert!(matches!(
+            events.next().await,
+            Some(Ok(WsEvent::Connection(ConnectionEvent::Disconnected { .. })))
+        ));
+        assert_eq!(started.elapsed(), Duration::from_secs(15));
+        assert!(events.next().await.is_none());
+        assert_eq!(calls.lock().unwrap()[1..], ["ping", "close"]);
+    }
+
+    #[tokio::test]
+    async fn test_events_are_typed() {
+        let (conn, _) = FakeConnection::new(
+            false,
+            vec![
+                Ok(Frame::Text(TOKEN_PAIR_JSON.to_string())),
+                Ok(Frame::Text(r#"{"status":"subscribed"}"#.to_string())),
+                Ok(Frame::Text("not json".to_string())),
+            ],
+        );
+        let client = VyperWebsocketClient::new("test_api_key".to_string());
+        client.conn.lock().await.insert(FeedType::MigrationEvents, Box::new(conn));
+
+        let events: Vec<_> = client.events().take(3).collect().await;
+        assert!(matches!(&events[0], Ok(WsEvent::MigrationEvent(pair)) if pair.market_id == "market1"));
+        assert!(matches!(&events[1], Ok(WsEvent::Control(value)) if value["status"] == "subscribed"));
+        assert!(matches!(&events[2], Err(VyperError::DeserializeError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_events_without_connection() {
+        let client = VyperWebsocketClient::new("test_api_key".to_string());
+        let events: Vec<_> = client.events().collect().await;
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0], Err(VyperError::WebsocketError { .. })));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_events_include_reconnects() {
+        let (conn, calls) = FakeConnection::new(
+            false,
+            vec![
+                Ok(Frame::Text(TOKEN_PAIR_JSON.to_string())),
+                Err("connection reset".into()),
+            ],
+        );
+        let client = VyperWebsocketClient::new("test_api_key".to_string()).with_reconnect_policy(ReconnectPolicy {
+            max_attempts: Some(1),
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(1),
+            jitter: 0.0,
+        });
+        client.conn.lock().await.insert(FeedType::TokenEvents, Box::new(conn));
+
+        let events: Vec<_> = client.events().take(4).map(Result::unwrap).collect().await;
+        assert!(matches!(events[0], WsEvent::TokenEvent(_)));
+        assert!(matches!(events[1], WsEvent::Connection(ConnectionEvent::Disconnected { .. })));
+        assert!(matches!(events[2], WsEvent::Connection(ConnectionEvent::Reconnecting { attempt: 1, .. })));
+        assert!(matches!(events[3], WsEvent::Connection(ConnectionEvent::Reconnected { attempt: 1, .. })));
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec!["connect wss://api.vyper.trade/api/v1/ws/token-events?apiKey=test_api_key"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_commands_while_listening() {
+        let (conn, calls) = FakeConnection::new(false, vec![Ok(Frame::Text(TOKEN_PAIR_JSON.to_string()))]);
+        let client = VyperWebsocketClient::new("test_api_key".to_string());
+        client.conn.lock().await.insert(FeedType::TokenEvents, Box::new(conn));
+
+        let mut events = client.events();
+        assert!(matches!(events.next().await, Some(Ok(WsEvent::TokenEvent(_)))));
+
+        let message = TokenSubscriptionMessage {
+            action: SubscriptionMessageType::Subscribe,
+            types: vec![SubscriptionType::RaydiumCpmmTokens],
+        };
+        tokio::time::timeout(Duration::from_secs(1), async {
+            client.subscribe(FeedType::TokenEvents, message).await.unwrap();
+            client.ping().await.unwrap();
+            client.disconnect().await.unwrap();
+        })
+        .await
+        .expect("commands should not wait for the event stream");
+
+        assert!(events.next().await.is_none());
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![
+                r#"send {"action":"subscribe","types":["RaydiumCpmmTokens"]}"#,
+                "ping",
+                "close",
+            ]
+        );
+        assert!(matches!(client.ping().await, Ok(())));
+        assert!(client.subscribe(FeedType::TokenEvents, serde_json::json!({})).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_second_consumer_is_rejected() {
+        let (conn, _) = FakeConnection::new(false, vec![Ok(Frame::Text(TOKEN_PAIR_JSON.to_string()))]);
+        let client = VyperWebsocketClient::new("test_api_key".to_string());
+        client.conn.lock().await.insert(FeedType::TokenEvents, Box::new(conn));
+
+        let mut events = client.events();
+        assert!(matches!(events.next().await, Some(Ok(WsEvent::TokenEvent(_)))));
+
+        let second: Vec<_> = tokio::time::timeout(Duration::from_secs(1), client.feed_events().collect())
+            .await
+            .expect("a second stream should not wait for the first");
+        assert_eq!(second.len(), 1);
+        assert!(matches!(&second[0], Err(VyperError::WebsocketError { message, .. }) if message == "Already listening"));
+        let listened = tokio::time::timeout(Duration::from_secs(1), client.listen())
+            .await
+            .expect("listen should not wait for the first stream");
+        assert!(matches!(listened, Err(VyperError::WebsocketError { .. })));
+
+        client.disconnect().await.unwrap();
+        assert!(events.next().await.is_none());
+    }
+
+    fn chain_action_json() -> String {
+        serde_json::json!({
+            "signer": "s", "transactionId": "tx", "marketId": "m", "actionType": "buy", "tokenAmount": 1.0,
+            "assetAmount": 1.0, "tokenPriceUsd": 1.0, "tokenPriceAsset": 1.0, "tokenMarketCapAsset": 1.0,
+            "tokenMarketCapUsd": 1.0, "tokenLiquidityAsset": 1.0, "tokenLiquidityUsd": 1.0, "pooledToken": 1.0,
+            "pooledAsset": 1.0, "actionTimestamp": 0
+        })
+        .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_multiple_feeds() {
+        let (tokens, token_calls) = FakeConnection::new(false, vec![Ok(Frame::Text(TOKEN_PAIR_JSON.to_string()))]);
+        let (wallets, wallet_calls) = FakeConnection::new(false, vec![Ok(Frame::Text(chain_action_json()))]);
+        let client = VyperWebsocketClient::new("test_api_key".to_string());
+        client.conn.lock().await.insert(FeedType::TokenEvents, Box::new(tokens));
+        client.conn.lock().await.insert(FeedType::WalletEvents, Box::new(wallets));
+        assert_eq!(client.connected_feeds().await, vec![FeedType::TokenEvents, FeedType::WalletEvents]);
+
+        let mut events = client.feed_events();
+        let mut received = Vec::new();
+        for _ in 0..2 {
+            match events.next().await.unwrap().unwrap() {
+                FeedEvent {
+                    feed_type: FeedType::TokenEvents,
+                    event: WsEvent::TokenEvent(_),
+                } => received.push(FeedType::TokenEvents),
+                FeedEvent {
+                    feed_type: FeedType::WalletEvents,
+                    event: WsEvent::WalletEvent(_),
+                } => received.push(FeedType::WalletEvents),
+                event => panic!("unexpected event {:?}", event),
+            }
+        }
+        received.sort_by_key(|feed_type| feed_type.to_string());
+        assert_eq!(received, vec![FeedType::TokenEvents, FeedType::WalletEvents]);
+
+        let wallet = WalletSubscriptionMessage {
+            action: SubscriptionMessageType::Subscribe,
+            wallets: vec!["w1".to_string()],
+        };
+        client.subscribe(FeedType::WalletEvents, wallet).await.unwrap();
+        assert!(client.subscribe(FeedType::MigrationEvents, serde_json::json!({})).await.is_err());
+        assert_eq!(*wallet_calls.lock().unwrap(), vec![r#"send {"action":"subscribe","wallets":["w1"]}"#]);
+        assert!(token_calls.lock().unwrap().is_empty());
+
+        client.disconnect_feed(FeedType::TokenEvents).await.unwrap();
+        assert_eq!(*token_calls.lock().unwrap(), vec!["close"]);
+        assert_eq!(client.connected_feeds().await, vec![FeedType::WalletEvents]);
+
+        client.disconnect().await.unwrap();
+        assert!(events.next().await.is_none());
+        assert!(client.connected_feeds().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_listen_stops_on_first_feed_failure() {
+        let (tokens, _) = FakeConnection::new(false, vec![Err("connection reset".into())]);
+        let (wallets, wallet_calls) = FakeConnection::new(false, Vec::new());
+        let client = VyperWebsocketClient::new("test_api_key".to_string()).with_reconnect_policy(ReconnectPolicy {
+            max_attempts: Some(0),
+            ..Default::default()
+        });
+        client.conn.lock().await.insert(FeedType::TokenEvents, Box::new(tokens));
+        client.conn.lock().await.insert(FeedType::WalletEvents, Box::new(wallets));
+
+        let result = tokio::time::timeout(Duration::from_secs(1), client.listen())
+            .await
+            .expect("listen should return on the failed feed");
+        assert!(matches!(result, Err(VyperError::WebsocketError { ref message, .. }) if message == "connection reset"));
+        assert_eq!(client.connected_feeds().await, vec![FeedType::WalletEvents]);
+
+        client.disconnect().await.unwrap();
+        assert_eq!(*wallet_calls.lock().unwrap(), vec!["close"]);
+    }
+
+    async fn next_event(events: &mut BoxStream<'_, Result<FeedEvent, VyperError>>) -> FeedEvent {
+        tokio::time::timeout(Duration::from_secs(1), events.next())
+            .await
+            .expect("event should be delivered")
+            .unwrap()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_connect_while_streaming() {
+        let (tokens, token_calls) = FakeConnection::new(false, vec![Ok(Frame::Text(TOKEN_PAIR_JSON.to_string()))]);
+        let client = VyperWebsocketClient::new("test_api_key".to_string());
+        client.conn.lock().await.insert(FeedType::TokenEvents, Box::new(tokens));
+
+        let mut events = client.feed_events();
+        assert_eq!(next_event(&mut events).await.feed_type, FeedType::TokenEvents);
+
+        // Replacing the only driven connection keeps the stream open.
+        let (tokens, _) = FakeConnection::new(false, vec![Ok(Frame::Text(TOKEN_PAIR_JSON.to_string()))]);
+        client.conn.lock().await.insert(FeedType::TokenEvents, Box::new(tokens));
+        client.connect(FeedType::TokenEvents).await.unwrap();
+        assert_eq!(*token_calls.lock().unwrap(), vec!["close"]);
+        assert_eq!(next_event(&mut events).await.feed_type, FeedType::TokenEvents);
+
+        // A feed connected later is merged into the running stream.
+        let (wallets, _) = FakeConnection::new(false, vec![Ok(Frame::Text(chain_action_json()))]);
+        client.conn.lock().await.insert(FeedType::WalletEvents, Box::new(wallets));
+        client.connect(FeedType::WalletEvents).await.unwrap();
+        assert!(matches!(
+            next_event(&mut events).await,
+            FeedEvent {
+                feed_type: FeedType::WalletEvents,
+                event: WsEvent::WalletEvent(_),
+            }
+        ));
+        assert_eq!(client.connected_feeds().await, vec![FeedType::TokenEvents, FeedType::WalletEvents]);
+
+        client.disconnect().await.unwrap();
+        assert!(events.next().await.is_none());
+    }
+}
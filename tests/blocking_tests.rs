@@ -0,0 +1,34 @@
+#![cfg(feature = "blocking")]
+
+use vyper_client_rs::{
+    blocking::VyperClient,
+    cassette::Cassette,
+    types::{ChainId, TokenPairsParams},
+};
+
+#[test]
+fn test_blocking_replay_token_pairs() {
+    let path = format!("{}/tests/cassettes/solana_market.json", env!("CARGO_MANIFEST_DIR"));
+    let client = VyperClient::builder()
+        .api_key("replay")
+        .cassette(Cassette::replay(path).expect("Failed to load cassette"))
+        .build_blocking()
+        .expect("Failed to build client");
+
+    let params = TokenPairsParams {
+        chain_ids: Some(vec![ChainId::Solana]),
+        page: Some(1),
+        ..Default::default()
+    };
+    let pairs: Vec<_> = client
+        .token_pairs_iter(params)
+        .collect::<Result<_, _>>()
+        .expect("Failed to iterate token pairs");
+    assert_eq!(pairs.len(), 1);
+    assert_eq!(pairs[0].symbol, "ECAT");
+
+    let top_traders = client
+        .get_top_traders(&pairs[0].market_id, pairs[0].chain_id)
+        .expect("Failed to get top traders");
+    assert_eq!(top_traders.len(), 1);
+}
@@ -0,0 +1,42 @@
+use vyper_client_rs::{
+    cassette::Cassette,
+    client::VyperClient,
+    types::{ChainId, TokenPairsParams},
+};
+
+fn replay_client(cassette: &str) -> VyperClient {
+    let path = format!("{}/tests/cassettes/{}", env!("CARGO_MANIFEST_DIR"), cassette);
+    VyperClient::builder()
+        .api_key("replay")
+        .cassette(Cassette::replay(path).expect("Failed to load cassette"))
+        .build()
+        .expect("Failed to build client")
+}
+
+#[tokio::test]
+async fn test_replay_token_pairs_and_top_traders() {
+    // Synthetic sample data captured from a mock server, not the live API.
+    let client = replay_client("solana_market.json");
+
+    let params = TokenPairsParams {
+        chain_ids: Some(vec![ChainId::Solana]),
+        page: Some(1),
+        ..Default::default()
+    };
+    let response = client.get_token_pairs_with_meta(params).await.expect("Failed to get token pairs");
+    assert_eq!(response.request_id.as_deref(), Some("cassette-pairs-1"));
+    let token_pairs = response.into_data();
+    assert!(!token_pairs.has_next);
+    assert_eq!(token_pairs.pairs.len(), 1);
+
+    let pair = &token_pairs.pairs[0];
+    assert_eq!(pair.chain_id, ChainId::Solana);
+    assert_eq!(pair.symbol, "ECAT");
+
+    let top_traders = client
+        .get_top_traders(&pair.market_id, pair.chain_id)
+        .await
+        .expect("Failed to get top traders");
+    assert_eq!(top_traders.len(), 1);
+    assert_eq!(top_traders[0].wallet_address, "HN7cABqLq46Es1jh92dQQisAq662SmxELLLsHHe4YWrH");
+}
@@ -1,5 +1,4 @@
 use std::env;
-use tokio;
 use dotenv::dotenv; 
 use vyper_client_rs::{
     websocket::{VyperWebsocketClient, FeedType, TokenSubscriptionMessage, SubscriptionMessageType, SubscriptionType},
@@ -16,9 +15,9 @@ async fn test_vyper_client_integration() {
     let chain_ids = client.get_chain_ids().await.expect("Failed to get chain IDs");
     assert!(!chain_ids.is_empty(), "Chain IDs should not be empty");
 
-    use vyper_client_rs::types::TokenPairsParams;
+    use vyper_client_rs::types::{ChainId, TokenPairsParams};
     let params = TokenPairsParams {
-        chain_ids: Some(vec![900]),
+        chain_ids: Some(vec![ChainId::Solana]),
         page: Some(1),
         ..Default::default()
     };